@@ -1,4 +1,9 @@
-import {greet, age_comparator} from './pkg/rust_wasm_package'
+import {greet, age_comparator, age_comparator_in, check_age, format_ordinal, rule_history} from './pkg/rust_wasm_package'
 
 console.log(greet(`mani`))
-console.log(age_comparator(18))
\ No newline at end of file
+console.log(age_comparator(18))
+console.log(age_comparator_in(`GB-SCT`, 16).toJSON())
+console.log(check_age(17).yearsUntilEligible)
+console.log(check_age(15).yearsUntilEligibleMessage(`en`))
+console.log(format_ordinal(18, `fr`))
+console.log(rule_history(`JP`).map(rule => [rule.id, rule.effectiveFrom, rule.effectiveTo]))
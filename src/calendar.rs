@@ -0,0 +1,625 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::countdown::{self, Countdown};
+use crate::dates;
+use crate::eligibility::{self, EligibilityResult};
+use crate::error::EligibilityError;
+use crate::js;
+use crate::rules::{self, VotingAgeRule};
+
+#[wasm_bindgen(typescript_custom_section)]
+const ELECTION_JSON: &'static str = r#"
+export interface ElectionJSON {
+  id: string;
+  name: string | null;
+  date: string;
+  jurisdiction: string;
+  type: "general" | "primary" | "runoff" | "local" | "referendum" | "special";
+  registrationDeadline: string | null;
+  minimumAge: number | null;
+  linkedElection: string | null;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ElectionJSON")]
+    pub type ElectionJson;
+}
+
+/// The kind of vote being held.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ElectionType {
+    General,
+    Primary,
+    Runoff,
+    Local,
+    Referendum,
+    Special,
+}
+
+impl ElectionType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ElectionType::General => "general",
+            ElectionType::Primary => "primary",
+            ElectionType::Runoff => "runoff",
+            ElectionType::Local => "local",
+            ElectionType::Referendum => "referendum",
+            ElectionType::Special => "special",
+        }
+    }
+
+    /// Parses a type name, ignoring case.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "general" => Some(ElectionType::General),
+            "primary" => Some(ElectionType::Primary),
+            "runoff" => Some(ElectionType::Runoff),
+            "local" => Some(ElectionType::Local),
+            "referendum" => Some(ElectionType::Referendum),
+            "special" => Some(ElectionType::Special),
+            _ => None,
+        }
+    }
+}
+
+/// One row of a CSV calendar or one object of a JSON calendar, before validation.
+#[derive(Debug, Deserialize)]
+struct ElectionRecord {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+    date: String,
+    jurisdiction: String,
+    #[serde(
+        default,
+        rename = "type",
+        alias = "election_type",
+        alias = "electionType"
+    )]
+    kind: Option<String>,
+    #[serde(default, alias = "registrationDeadline")]
+    registration_deadline: Option<String>,
+    #[serde(default, alias = "minimumAge")]
+    minimum_age: Option<u8>,
+    #[serde(default, alias = "linkedElection")]
+    linked_election: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonCalendar {
+    elections: Vec<ElectionRecord>,
+}
+
+/// A scheduled election.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Election {
+    id: String,
+    name: Option<String>,
+    #[serde(serialize_with = "dates::serialize_iso")]
+    date: NaiveDate,
+    jurisdiction: String,
+    #[serde(rename = "type")]
+    kind: ElectionType,
+    #[serde(serialize_with = "dates::serialize_iso_opt")]
+    registration_deadline: Option<NaiveDate>,
+    minimum_age: Option<u8>,
+    linked_election: Option<String>,
+}
+
+impl Election {
+    fn from_record(index: usize, record: ElectionRecord) -> Result<Self, EligibilityError> {
+        let invalid = |message: String| EligibilityError::InvalidElection { index, message };
+        let date =
+            dates::parse_iso_date(&record.date).map_err(|error| invalid(error.to_string()))?;
+        let jurisdiction = rules::lookup_as_of(&record.jurisdiction, date)
+            .map(|_| rules::normalize_jurisdiction(&record.jurisdiction))
+            .map_err(|error| invalid(error.to_string()))?;
+        let kind = match record.kind.as_deref().map(str::trim) {
+            None | Some("") => ElectionType::General,
+            Some(name) => ElectionType::parse(name)
+                .ok_or_else(|| invalid(format!("unknown election type `{}`", name)))?,
+        };
+        let registration_deadline = match record.registration_deadline.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(text) => {
+                Some(dates::parse_iso_date(text).map_err(|error| invalid(error.to_string()))?)
+            }
+        };
+        if registration_deadline.is_some_and(|deadline| deadline > date) {
+            return Err(invalid(
+                "registration deadline is after the election".to_string(),
+            ));
+        }
+        let id = match record.id.as_deref().map(str::trim) {
+            None | Some("") => format!("{}/{}/{}", jurisdiction, date, kind.as_str()),
+            Some(id) => id.to_string(),
+        };
+        Ok(Election {
+            id,
+            name: record.name.filter(|name| !name.trim().is_empty()),
+            date,
+            jurisdiction,
+            kind,
+            registration_deadline,
+            minimum_age: record.minimum_age,
+            linked_election: record
+                .linked_election
+                .map(|id| id.trim().to_string())
+                .filter(|id| !id.is_empty()),
+        })
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn registration_deadline(&self) -> Option<NaiveDate> {
+        self.registration_deadline
+    }
+
+    /// Whether voters of `jurisdiction` take part: a `US` election includes `US-CA` voters.
+    pub fn covers(&self, jurisdiction: &str) -> bool {
+        let code = rules::normalize_jurisdiction(jurisdiction);
+        code == self.jurisdiction
+            || code
+                .strip_prefix(self.jurisdiction.as_str())
+                .is_some_and(|rest| rest.starts_with('-'))
+    }
+
+    /// The voting-age rule for this election: the jurisdiction's rule in force on the election
+    /// date, or its minimum age override.
+    pub fn rule(&self) -> Result<VotingAgeRule, EligibilityError> {
+        let rule = rules::lookup_as_of(&self.jurisdiction, self.date)?;
+        Ok(match self.minimum_age {
+            Some(age) => rule.with_voting_age(age, &self.id),
+            None => rule,
+        })
+    }
+
+    /// Checks whether someone born on `dob` is old enough to vote in this election on its own,
+    /// without regard to a linked election.
+    pub fn check(&self, dob: NaiveDate) -> Result<EligibilityResult, EligibilityError> {
+        eligibility::evaluate_dob(dob, self.date, &self.rule()?)
+    }
+}
+
+#[wasm_bindgen]
+impl Election {
+    #[wasm_bindgen(getter)]
+    pub fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> Option<String> {
+        self.name.clone()
+    }
+
+    /// The election date, as `YYYY-MM-DD`.
+    #[wasm_bindgen(getter, js_name = date)]
+    pub fn iso_date(&self) -> String {
+        self.date.to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn jurisdiction(&self) -> String {
+        self.jurisdiction.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = "type")]
+    pub fn kind(&self) -> ElectionType {
+        self.kind
+    }
+
+    /// The last day to register, as `YYYY-MM-DD`.
+    #[wasm_bindgen(getter, js_name = registrationDeadline)]
+    pub fn iso_registration_deadline(&self) -> Option<String> {
+        self.registration_deadline.map(|date| date.to_string())
+    }
+
+    /// Voting age for this election when it differs from the jurisdiction's, as in primaries.
+    #[wasm_bindgen(getter, js_name = minimumAge)]
+    pub fn minimum_age(&self) -> Option<u8> {
+        self.minimum_age
+    }
+
+    /// Id of the general election this primary nominates for.
+    #[wasm_bindgen(getter, js_name = linkedElection)]
+    pub fn linked_election(&self) -> Option<String> {
+        self.linked_election.clone()
+    }
+
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json(&self) -> Result<ElectionJson, JsError> {
+        Ok(js::to_js(self)?.unchecked_into())
+    }
+}
+
+/// A set of scheduled elections, kept in date order.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ElectionCalendar {
+    elections: Vec<Election>,
+}
+
+impl ElectionCalendar {
+    fn from_records(
+        records: impl IntoIterator<Item = Result<ElectionRecord, EligibilityError>>,
+    ) -> Result<Self, EligibilityError> {
+        let mut elections = records
+            .into_iter()
+            .enumerate()
+            .map(|(index, record)| Election::from_record(index + 1, record?))
+            .collect::<Result<Vec<_>, _>>()?;
+        if let Some(duplicate) = elections
+            .iter()
+            .enumerate()
+            .find(|(index, election)| elections[..*index].iter().any(|e| e.id == election.id))
+        {
+            return Err(EligibilityError::InvalidElection {
+                index: duplicate.0 + 1,
+                message: format!("duplicate election id `{}`", duplicate.1.id),
+            });
+        }
+        for (index, election) in elections.iter().enumerate() {
+            let Some(id) = &election.linked_election else {
+                continue;
+            };
+            let invalid = |message: String| EligibilityError::InvalidElection {
+                index: index + 1,
+                message,
+            };
+            let linked = elections
+                .iter()
+                .find(|linked| linked.id == *id)
+                .ok_or_else(|| invalid(format!("linked election `{}` does not exist", id)))?;
+            if linked.date < election.date {
+                return Err(invalid(format!(
+                    "linked election `{}` is before this election",
+                    id
+                )));
+            }
+        }
+        elections.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.id.cmp(&b.id)));
+        let mut calendar = ElectionCalendar { elections };
+        calendar.link_primaries();
+        Ok(calendar)
+    }
+
+    /// Links every primary without an explicit `linked_election` to the first general
+    /// election on or after it that covers its jurisdiction.
+    fn link_primaries(&mut self) {
+        for index in 0..self.elections.len() {
+            let election = &self.elections[index];
+            if election.kind != ElectionType::Primary || election.linked_election.is_some() {
+                continue;
+            }
+            let linked = self.elections[index..]
+                .iter()
+                .find(|general| {
+                    general.kind == ElectionType::General && general.covers(&election.jurisdiction)
+                })
+                .map(|general| general.id.clone());
+            self.elections[index].linked_election = linked;
+        }
+    }
+
+    /// Checks someone born on `dob` against `election`, letting a linked general election
+    /// decide a primary where the jurisdiction's rule allows it.
+    pub fn check(
+        &self,
+        dob: NaiveDate,
+        election: &Election,
+    ) -> Result<EligibilityResult, EligibilityError> {
+        let rule = election.rule()?;
+        match election
+            .linked_election
+            .as_deref()
+            .and_then(|id| self.find(id))
+        {
+            Some(linked) => {
+                eligibility::evaluate_linked(dob, election.date, &linked.id, linked.date, &rule)
+            }
+            None => eligibility::evaluate_dob(dob, election.date, &rule),
+        }
+    }
+
+    /// Reads a CSV calendar with `date` and `jurisdiction` columns and optional `id`, `name`,
+    /// `type`, `registration_deadline` and `minimum_age` columns.
+    pub fn from_csv(text: &str) -> Result<Self, EligibilityError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(text.as_bytes());
+        let records: Vec<_> = reader
+            .deserialize::<ElectionRecord>()
+            .map(|record| record.map_err(EligibilityError::from))
+            .collect();
+        Self::from_records(records)
+    }
+
+    /// Reads a JSON array of elections, or an object with an `elections` array, using the
+    /// CSV column names or their camelCase forms.
+    pub fn from_json(text: &str) -> Result<Self, EligibilityError> {
+        let records: Vec<ElectionRecord> = if text.trim_start().starts_with('[') {
+            serde_json::from_str(text)?
+        } else {
+            serde_json::from_str::<JsonCalendar>(text)?.elections
+        };
+        Self::from_records(records.into_iter().map(Ok))
+    }
+
+    pub fn elections(&self) -> &[Election] {
+        &self.elections
+    }
+
+    pub fn find(&self, id: &str) -> Option<&Election> {
+        self.elections.iter().find(|election| election.id == id)
+    }
+
+    /// Elections covering `jurisdiction` on or after `from`, soonest first.
+    pub fn upcoming(
+        &self,
+        jurisdiction: &str,
+        from: NaiveDate,
+    ) -> impl Iterator<Item = &Election> + '_ {
+        let jurisdiction = rules::normalize_jurisdiction(jurisdiction);
+        self.elections
+            .iter()
+            .filter(move |election| election.date >= from && election.covers(&jurisdiction))
+    }
+
+    /// The first election covering `jurisdiction` on or after `from`, optionally of one type.
+    pub fn next(
+        &self,
+        jurisdiction: &str,
+        from: NaiveDate,
+        kind: Option<ElectionType>,
+    ) -> Option<&Election> {
+        self.upcoming(jurisdiction, from)
+            .find(|election| kind.is_none_or(|kind| election.kind == kind))
+    }
+
+    /// [`countdown::countdown_in`] with this calendar's elections for `jurisdiction`.
+    pub fn countdown(
+        &self,
+        jurisdiction: &str,
+        dob: NaiveDate,
+        reference: NaiveDate,
+    ) -> Result<Countdown, EligibilityError> {
+        let dates: Vec<NaiveDate> = self
+            .upcoming(jurisdiction, reference)
+            .map(Election::date)
+            .collect();
+        countdown::countdown_in(jurisdiction, dob, reference, &dates)
+    }
+}
+
+#[wasm_bindgen]
+impl ElectionCalendar {
+    #[wasm_bindgen(js_name = fromCsv)]
+    pub fn js_from_csv(text: &str) -> Result<ElectionCalendar, JsError> {
+        Ok(Self::from_csv(text)?)
+    }
+
+    #[wasm_bindgen(js_name = fromJson)]
+    pub fn js_from_json(text: &str) -> Result<ElectionCalendar, JsError> {
+        Ok(Self::from_json(text)?)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.elections.len()
+    }
+
+    /// Every election, in date order.
+    #[wasm_bindgen(js_name = elections)]
+    pub fn js_elections(&self) -> Vec<Election> {
+        self.elections.clone()
+    }
+
+    #[wasm_bindgen(js_name = get)]
+    pub fn js_get(&self, id: &str) -> Option<Election> {
+        self.find(id).cloned()
+    }
+
+    /// The first election covering `jurisdiction` on or after `from` (a string or `Date`).
+    #[wasm_bindgen(js_name = nextElection)]
+    pub fn next_election(
+        &self,
+        jurisdiction: &str,
+        from: JsValue,
+        kind: Option<ElectionType>,
+    ) -> Result<Option<Election>, JsError> {
+        let from = dates::date_from_js(&from)?;
+        Ok(self.next(jurisdiction, from, kind).cloned())
+    }
+
+    /// Elections covering `jurisdiction` on or after `from`, soonest first.
+    #[wasm_bindgen(js_name = upcomingElections)]
+    pub fn upcoming_elections(
+        &self,
+        jurisdiction: &str,
+        from: JsValue,
+    ) -> Result<Vec<Election>, JsError> {
+        let from = dates::date_from_js(&from)?;
+        Ok(self.upcoming(jurisdiction, from).cloned().collect())
+    }
+
+    /// Checks someone born on `dateOfBirth` against the election with id `electionId`.
+    #[wasm_bindgen(js_name = checkEligibility)]
+    pub fn check_eligibility(
+        &self,
+        date_of_birth: JsValue,
+        election_id: &str,
+    ) -> Result<EligibilityResult, JsError> {
+        let election = self
+            .find(election_id)
+            .ok_or_else(|| JsError::new(&format!("no election has id `{}`", election_id)))?;
+        Ok(self.check(dates::date_from_js(&date_of_birth)?, election)?)
+    }
+
+    /// Whether someone born on `dateOfBirth` is old enough for the election `electionId`.
+    #[wasm_bindgen(js_name = isEligibleFor)]
+    pub fn is_eligible_for(
+        &self,
+        date_of_birth: JsValue,
+        election_id: &str,
+    ) -> Result<bool, JsError> {
+        Ok(self
+            .check_eligibility(date_of_birth, election_id)?
+            .eligible())
+    }
+
+    /// `eligibility_countdown` using this calendar's elections.
+    #[wasm_bindgen(js_name = countdown)]
+    pub fn js_countdown(
+        &self,
+        jurisdiction: &str,
+        date_of_birth: JsValue,
+        reference: JsValue,
+    ) -> Result<Countdown, JsError> {
+        let dob = dates::date_from_js(&date_of_birth)?;
+        let reference = dates::date_from_js(&reference)?;
+        Ok(self.countdown(jurisdiction, dob, reference)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(text: &str) -> NaiveDate {
+        dates::parse_iso_date(text).unwrap()
+    }
+
+    const CSV: &str = "\
+id,name,date,jurisdiction,type,registration_deadline,minimum_age
+,General,2026-11-03,US,general,2026-10-13,
+ct-primary,Primary,2026-08-11,us_ct,Primary,,
+,Town meeting,2026-05-05,US-CT,local,,16
+";
+
+    fn invalid(result: Result<ElectionCalendar, EligibilityError>) -> (usize, String) {
+        match result {
+            Err(EligibilityError::InvalidElection { index, message }) => (index, message),
+            other => panic!("expected an invalid election, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn reads_csv_in_date_order_with_defaults() {
+        let calendar = ElectionCalendar::from_csv(CSV).unwrap();
+        let ids: Vec<_> = calendar.elections().iter().map(Election::id).collect();
+        assert_eq!(
+            ids,
+            [
+                "US-CT/2026-05-05/local",
+                "ct-primary",
+                "US/2026-11-03/general"
+            ]
+        );
+        let general = calendar.find("US/2026-11-03/general").unwrap();
+        assert_eq!(general.registration_deadline(), Some(date("2026-10-13")));
+        assert_eq!(general.name().as_deref(), Some("General"));
+        assert_eq!(calendar.elections()[0].minimum_age(), Some(16));
+    }
+
+    #[test]
+    fn reads_json_arrays_and_objects() {
+        let array = ElectionCalendar::from_json(
+            r#"[{"date": "2026-11-03", "jurisdiction": "US", "registrationDeadline": "2026-10-13"}]"#,
+        )
+        .unwrap();
+        let object = ElectionCalendar::from_json(
+            r#"{"elections": [{"date": "2026-11-03", "jurisdiction": "us", "registration_deadline": "2026-10-13"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(array, object);
+        assert_eq!(array.elections()[0].kind(), ElectionType::General);
+    }
+
+    #[test]
+    fn links_primaries_to_the_next_covering_general() {
+        let calendar = ElectionCalendar::from_csv(CSV).unwrap();
+        let primary = calendar.find("ct-primary").unwrap();
+        assert_eq!(
+            primary.linked_election().as_deref(),
+            Some("US/2026-11-03/general")
+        );
+
+        // Turns 18 between the primary and the general election.
+        let dob = date("2008-10-01");
+        assert!(!primary.check(dob).unwrap().eligible());
+        let result = calendar.check(dob, primary).unwrap();
+        assert!(result.eligible());
+        assert_eq!(result.linked_election().unwrap().age(), 18);
+    }
+
+    #[test]
+    fn applies_an_election_minimum_age() {
+        let calendar = ElectionCalendar::from_csv(CSV).unwrap();
+        let town = &calendar.elections()[0];
+        assert!(town.check(date("2010-01-01")).unwrap().eligible());
+        assert!(!town.check(date("2010-06-01")).unwrap().eligible());
+    }
+
+    #[test]
+    fn finds_upcoming_elections_for_a_subdivision() {
+        let calendar = ElectionCalendar::from_csv(CSV).unwrap();
+        let from = date("2026-06-01");
+        let upcoming: Vec<_> = calendar.upcoming("us-ct", from).map(Election::id).collect();
+        assert_eq!(upcoming, ["ct-primary", "US/2026-11-03/general"]);
+        assert_eq!(calendar.upcoming("US-NY", from).count(), 1);
+        assert_eq!(
+            calendar
+                .next("US-CT", from, Some(ElectionType::General))
+                .map(Election::id)
+                .as_deref(),
+            Some("US/2026-11-03/general")
+        );
+
+        let countdown = calendar
+            .countdown("US-CT", date("2008-10-01"), from)
+            .unwrap();
+        assert_eq!(countdown.first_election().as_deref(), Some("2026-11-03"));
+    }
+
+    #[test]
+    fn rejects_invalid_elections_by_position() {
+        let (index, message) = invalid(ElectionCalendar::from_csv(
+            "date,jurisdiction,type\n2026-11-03,US,general\n2026-11-04,US,plebiscite\n",
+        ));
+        assert_eq!(index, 2);
+        assert_eq!(message, "unknown election type `plebiscite`");
+
+        let (index, message) = invalid(ElectionCalendar::from_csv(
+            "date,jurisdiction,registration_deadline\n2026-11-03,US,2026-11-04\n",
+        ));
+        assert_eq!(index, 1);
+        assert_eq!(message, "registration deadline is after the election");
+
+        let (index, _) = invalid(ElectionCalendar::from_csv(
+            "date,jurisdiction\n2026-11-03,US\n2026-11-03,us\n",
+        ));
+        assert_eq!(index, 2);
+
+        let (_, message) = invalid(ElectionCalendar::from_json(
+            r#"[{"date": "2026-08-11", "jurisdiction": "US", "type": "primary", "linkedElection": "nope"}]"#,
+        ));
+        assert_eq!(message, "linked election `nope` does not exist");
+
+        let (_, message) = invalid(ElectionCalendar::from_json(
+            r#"[{"date": "2026-11-03", "jurisdiction": "ZZ"}]"#,
+        ));
+        assert_eq!(message, "unknown jurisdiction `ZZ`");
+    }
+}
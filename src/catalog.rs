@@ -0,0 +1,584 @@
+use crate::fluent;
+use crate::locale::{self, DEFAULT_LOCALE};
+use crate::plural;
+use crate::template;
+
+/// Built-in messages, keyed by locale then message id. Placeholders are written `{name}`,
+/// in the syntax of [`Template`](crate::template::Template).
+///
+/// A locale only needs the messages that differ from its fallbacks, so `pt-BR` inherits
+/// anything it leaves out from `pt`, and every locale inherits from `en`. Messages that
+/// agree with a count come in `id.<plural category>` variants, with `id.other` or plain
+/// `id` as the catch-all.
+const CATALOGS: &[(&str, &[(&str, &str)])] = &[
+    (
+        "ar",
+        &[
+            ("greeting", "مرحبًا، {name}!"),
+            ("eligible.few", "عمرك {age} سنوات، يحق لك التصويت."),
+            ("eligible.other", "عمرك {age} سنة، يحق لك التصويت."),
+            ("not-eligible.one", "عمرك سنة واحدة، لا يحق لك التصويت بعد (الحد الأدنى للسن: {threshold})."),
+            ("not-eligible.two", "عمرك سنتان، لا يحق لك التصويت بعد (الحد الأدنى للسن: {threshold})."),
+            ("not-eligible.few", "عمرك {age} سنوات، لا يحق لك التصويت بعد (الحد الأدنى للسن: {threshold})."),
+            ("not-eligible.other", "عمرك {age} سنة، لا يحق لك التصويت بعد (الحد الأدنى للسن: {threshold})."),
+            ("just-eligible", "تهانينا! لقد حصلت على حق التصويت."),
+            ("years-until-eligible.one", "يمكنك التصويت بعد سنة واحدة."),
+            ("years-until-eligible.two", "يمكنك التصويت بعد سنتين."),
+            ("years-until-eligible.few", "يمكنك التصويت بعد {years} سنوات."),
+            ("years-until-eligible.other", "يمكنك التصويت بعد {years} سنة."),
+        ],
+    ),
+    (
+        "bn",
+        &[
+            ("greeting", "নমস্কার, {name}!"),
+            ("eligible", "আপনার বয়স {age} বছর, আপনি ভোট দিতে পারবেন।"),
+            ("not-eligible", "আপনার বয়স {age} বছর, আপনি এখনও ভোট দিতে পারবেন না (ন্যূনতম বয়স: {threshold})।"),
+            ("just-eligible", "অভিনন্দন! আপনি ভোটাধিকার অর্জন করেছেন।"),
+            ("years-until-eligible.other", "আপনি {years} বছর পরে ভোট দিতে পারবেন।"),
+        ],
+    ),
+    (
+        "cs",
+        &[
+            ("greeting", "Ahoj, {name}!"),
+            ("eligible.one", "Je vám {age} rok, můžete volit."),
+            ("eligible.few", "Je vám {age} roky, můžete volit."),
+            ("eligible.other", "Je vám {age} let, můžete volit."),
+            ("not-eligible.one", "Je vám {age} rok, zatím nemůžete volit (minimální věk: {threshold})."),
+            ("not-eligible.few", "Je vám {age} roky, zatím nemůžete volit (minimální věk: {threshold})."),
+            ("not-eligible.other", "Je vám {age} let, zatím nemůžete volit (minimální věk: {threshold})."),
+            ("just-eligible", "Gratulujeme! Získali jste volební právo."),
+            ("years-until-eligible.one", "Volit budete moci za {years} rok."),
+            ("years-until-eligible.few", "Volit budete moci za {years} roky."),
+            ("years-until-eligible.other", "Volit budete moci za {years} let."),
+        ],
+    ),
+    ("da", &[("greeting", "Hej, {name}!")]),
+    (
+        "de",
+        &[
+            ("greeting", "Hallo, {name}!"),
+            ("eligible", "Sie sind {age} Jahre alt und dürfen wählen."),
+            ("not-eligible.one", "Sie sind {age} Jahr alt und dürfen noch nicht wählen (Mindestalter: {threshold})."),
+            ("not-eligible.other", "Sie sind {age} Jahre alt und dürfen noch nicht wählen (Mindestalter: {threshold})."),
+            ("just-eligible", "Glückwunsch! Sie haben das Wahlrecht erlangt."),
+            ("years-until-eligible.one", "Sie dürfen in {years} Jahr wählen, ab Ihrem {threshold|ordinal} Geburtstag."),
+            ("years-until-eligible.other", "Sie dürfen in {years} Jahren wählen, ab Ihrem {threshold|ordinal} Geburtstag."),
+            ("countdown", "Sie dürfen ab dem {date} wählen, in {#if years}{years} {years|plural:Jahr:Jahren}{#if months}{#if days}, {#else} und {/if}{#else}{#if days} und {/if}{/if}{/if}{#if months}{months} {months|plural:Monat:Monaten}{#if days} und {/if}{/if}{#if days}{days} {days|plural:Tag:Tagen}{/if}.{#if election} Die erste Wahl, an der Sie teilnehmen können, findet am {election} statt.{/if}"),
+            ("countdown-eligible", "Sie dürfen bereits wählen.{#if election} Die nächste Wahl findet am {election} statt.{/if}"),
+            ("registration-ineligible", "Sie sind {age} {age|plural:Jahr:Jahre} alt und können sich noch nicht ins Wählerverzeichnis eintragen."),
+            ("registration-preregister", "Sie sind {age} {age|plural:Jahr:Jahre} alt und können sich vorab ins Wählerverzeichnis eintragen; wählen dürfen Sie mit {threshold}."),
+            ("registration-register", "Sie sind {age} {age|plural:Jahr:Jahre} alt und können sich ins Wählerverzeichnis eintragen; wählen dürfen Sie mit {threshold}."),
+            ("registration-vote", "Sie sind {age} {age|plural:Jahr:Jahre} alt und dürfen wählen."),
+            ("greeting-morning", "Guten Morgen, {name}!"),
+            ("greeting-afternoon", "Guten Tag, {name}!"),
+            ("greeting-evening", "Guten Abend, {name}!"),
+            ("greeting-new-year", "Frohes neues Jahr, {name}!"),
+            ("greeting-birthday", "Alles Gute zum {age}. Geburtstag, {name}!"),
+            ("greeting-birthday-vote", "Alles Gute zum {age}. Geburtstag, {name}! Jetzt dürfen Sie wählen."),
+        ],
+    ),
+    ("el", &[("greeting", "Γεια σου, {name}!")]),
+    (
+        "en",
+        &[
+            ("greeting", "Hello, {name}!"),
+            ("greeting-formal", "Dear {name}"),
+            ("eligible", "You are {age} Eligible To Vote"),
+            ("not-eligible", "You are {age} Not Eligible To Vote"),
+            ("just-eligible", "Congrats You gained the Rights to Vote"),
+            ("years-until-eligible.one", "You can vote in {years} year, on your {threshold|ordinal} birthday."),
+            ("years-until-eligible.other", "You can vote in {years} years, on your {threshold|ordinal} birthday."),
+            ("countdown", "You can vote from {date}, in {#if years}{years} {years|plural:year:years}{#if months}{#if days}, {#else} and {/if}{#else}{#if days} and {/if}{/if}{/if}{#if months}{months} {months|plural:month:months}{#if days} and {/if}{/if}{#if days}{days} {days|plural:day:days}{/if}.{#if election} The first election you can vote in is on {election}.{/if}"),
+            ("countdown-eligible", "You can already vote.{#if election} The next election is on {election}.{/if}"),
+            ("registration-ineligible", "You are {age} and cannot register to vote yet."),
+            ("registration-preregister", "You are {age} and can pre-register to vote; you can vote at {threshold}."),
+            ("registration-register", "You are {age} and can register to vote; you can vote at {threshold}."),
+            ("registration-vote", "You are {age} and can vote."),
+            ("linked-election-eligible", "You are {age} on the day of this primary, but you will be {linkedAge} by the general election on {date}, so you can vote in this primary."),
+            ("linked-election-not-eligible", "You will only be {linkedAge} by the general election on {date}, so you cannot vote in this primary; the voting age is {threshold}."),
+            ("trace-rule", "Rule {rule} applies{#if jurisdiction} in {jurisdiction}{/if}{#if from} from {from}{/if}{#if to} until {to}{/if}: the voting age is {threshold}."),
+            ("trace-conviction-rule", "Conviction rules {rule} apply in {jurisdiction}."),
+            ("trace-age-less", "Age {age} is below the voting age of {threshold}."),
+            ("trace-age-equal", "Age {age} is exactly the voting age of {threshold}."),
+            ("trace-age-greater", "Age {age} is above the voting age of {threshold}."),
+            ("trace-date-less", "On {date} they are not yet {threshold}; they turn {threshold} on {birthday}."),
+            ("trace-date-equal", "They turn {threshold} on {date}, the day of the election."),
+            ("trace-date-greater", "They turned {threshold} on {birthday}, before {date}."),
+            ("trace-linked-less", "They turn {threshold} on {birthday}, after the linked general election on {date}."),
+            ("trace-linked-equal", "They turn {threshold} on {date}, the day of the linked general election."),
+            ("trace-linked-greater", "They turn {threshold} on {birthday}, before the linked general election on {date}."),
+            ("trace-record-allowed", "Their criminal record does not stop them voting."),
+            ("trace-record-barred", "Their criminal record stops them voting."),
+            ("trace-conditions-unmet", "The rule's conditions not met: {conditions}."),
+            ("trace-verdict-eligible", "Verdict: eligible to vote."),
+            ("trace-verdict-ineligible", "Verdict: not eligible to vote."),
+            ("greeting-morning", "Good morning, {name}!"),
+            ("greeting-afternoon", "Good afternoon, {name}!"),
+            ("greeting-evening", "Good evening, {name}!"),
+            ("greeting-new-year", "Happy New Year, {name}!"),
+            ("greeting-birthday", "Happy {age|ordinal} birthday, {name}!"),
+            ("greeting-birthday-vote", "Happy {age|ordinal} birthday, {name}, you can now vote!"),
+        ],
+    ),
+    (
+        "es",
+        &[
+            ("greeting", "¡Hola, {name}!"),
+            ("eligible", "Tienes {age} años: puedes votar."),
+            ("not-eligible.one", "Tienes {age} año: todavía no puedes votar (edad mínima: {threshold})."),
+            ("not-eligible.other", "Tienes {age} años: todavía no puedes votar (edad mínima: {threshold})."),
+            ("just-eligible", "¡Felicidades! Ya tienes derecho a votar."),
+            ("years-until-eligible.one", "Podrás votar dentro de {years} año, al cumplir {threshold}."),
+            ("years-until-eligible.other", "Podrás votar dentro de {years} años, al cumplir {threshold}."),
+            ("countdown", "Podrás votar a partir del {date}, dentro de {#if years}{years} {years|plural:año:años}{#if months}{#if days}, {#else} y {/if}{#else}{#if days} y {/if}{/if}{/if}{#if months}{months} {months|plural:mes:meses}{#if days} y {/if}{/if}{#if days}{days} {days|plural:día:días}{/if}.{#if election} La primera elección en la que podrás votar es el {election}.{/if}"),
+            ("countdown-eligible", "Ya puedes votar.{#if election} La próxima elección es el {election}.{/if}"),
+            ("registration-ineligible", "Tienes {age} {age|plural:año:años} y todavía no puedes inscribirte para votar."),
+            ("registration-preregister", "Tienes {age} {age|plural:año:años} y puedes preinscribirte para votar; podrás votar a los {threshold}."),
+            ("registration-register", "Tienes {age} {age|plural:año:años} y puedes inscribirte para votar; podrás votar a los {threshold}."),
+            ("registration-vote", "Tienes {age} {age|plural:año:años} y puedes votar."),
+            ("linked-election-eligible", "El día de estas primarias tendrás {age} años, pero cumplirás {linkedAge} antes de las elecciones generales del {date}, así que puedes votar en estas primarias."),
+            ("linked-election-not-eligible", "Solo tendrás {linkedAge} años en las elecciones generales del {date}, así que no puedes votar en estas primarias; la edad para votar es {threshold}."),
+            ("trace-rule", "Se aplica la regla {rule}{#if jurisdiction} en {jurisdiction}{/if}{#if from} desde el {from}{/if}{#if to} hasta el {to}{/if}: la edad para votar es {threshold}."),
+            ("trace-conviction-rule", "Se aplican las normas sobre condenas {rule} en {jurisdiction}."),
+            ("trace-age-less", "La edad, {age}, es inferior a la edad para votar, {threshold}."),
+            ("trace-age-equal", "La edad, {age}, es exactamente la edad para votar, {threshold}."),
+            ("trace-age-greater", "La edad, {age}, es superior a la edad para votar, {threshold}."),
+            ("trace-date-less", "El {date} aún no ha cumplido {threshold}; los cumple el {birthday}."),
+            ("trace-date-equal", "Cumple {threshold} el {date}, el mismo día de la elección."),
+            ("trace-date-greater", "Cumplió {threshold} el {birthday}, antes del {date}."),
+            ("trace-linked-less", "Cumple {threshold} el {birthday}, después de las elecciones generales vinculadas del {date}."),
+            ("trace-linked-equal", "Cumple {threshold} el {date}, el mismo día de las elecciones generales vinculadas."),
+            ("trace-linked-greater", "Cumple {threshold} el {birthday}, antes de las elecciones generales vinculadas del {date}."),
+            ("trace-record-allowed", "Sus antecedentes penales no le impiden votar."),
+            ("trace-record-barred", "Sus antecedentes penales le impiden votar."),
+            ("trace-conditions-unmet", "Condiciones de la regla que no se cumplen: {conditions}."),
+            ("trace-verdict-eligible", "Veredicto: puede votar."),
+            ("trace-verdict-ineligible", "Veredicto: no puede votar."),
+            ("greeting-morning", "¡Buenos días, {name}!"),
+            ("greeting-afternoon", "¡Buenas tardes, {name}!"),
+            ("greeting-evening", "¡Buenas noches, {name}!"),
+            ("greeting-new-year", "¡Feliz Año Nuevo, {name}!"),
+            ("greeting-birthday", "¡Feliz cumpleaños número {age}, {name}!"),
+            ("greeting-birthday-vote", "¡Feliz cumpleaños número {age}, {name}! Ya puedes votar."),
+        ],
+    ),
+    (
+        "fa",
+        &[
+            ("greeting", "سلام، {name}!"),
+            ("eligible", "شما {age} سال دارید و می‌توانید رأی دهید."),
+            ("not-eligible", "شما {age} سال دارید و هنوز نمی‌توانید رأی دهید (حداقل سن: {threshold})."),
+            ("just-eligible", "تبریک! شما حق رأی به دست آوردید."),
+            ("years-until-eligible.other", "{years} سال دیگر می‌توانید رأی دهید."),
+        ],
+    ),
+    ("fi", &[("greeting", "Hei, {name}!")]),
+    (
+        "fr",
+        &[
+            ("greeting", "Bonjour, {name}\u{202f}!"),
+            ("eligible", "Vous avez {age} ans\u{a0}: vous pouvez voter."),
+            ("not-eligible.one", "Vous avez {age} an\u{a0}: vous ne pouvez pas encore voter (âge minimum\u{a0}: {threshold} ans)."),
+            ("not-eligible.other", "Vous avez {age} ans\u{a0}: vous ne pouvez pas encore voter (âge minimum\u{a0}: {threshold} ans)."),
+            ("just-eligible", "Félicitations\u{202f}! Vous avez obtenu le droit de vote."),
+            ("years-until-eligible.one", "Vous pourrez voter dans {years} an, à vos {threshold} ans."),
+            ("years-until-eligible.other", "Vous pourrez voter dans {years} ans, à vos {threshold} ans."),
+            ("countdown", "Vous pourrez voter à partir du {date}, dans {#if years}{years} {years|plural:an:ans}{#if months}{#if days}, {#else} et {/if}{#else}{#if days} et {/if}{/if}{/if}{#if months}{months} mois{#if days} et {/if}{/if}{#if days}{days} {days|plural:jour:jours}{/if}.{#if election} La première élection à laquelle vous pourrez voter aura lieu le {election}.{/if}"),
+            ("countdown-eligible", "Vous pouvez déjà voter.{#if election} La prochaine élection aura lieu le {election}.{/if}"),
+            ("registration-ineligible", "Vous avez {age} {age|plural:an:ans} et ne pouvez pas encore vous inscrire sur les listes électorales."),
+            ("registration-preregister", "Vous avez {age} {age|plural:an:ans} et pouvez vous préinscrire sur les listes électorales\u{a0}; vous pourrez voter à {threshold}\u{a0}ans."),
+            ("registration-register", "Vous avez {age} {age|plural:an:ans} et pouvez vous inscrire sur les listes électorales\u{a0}; vous pourrez voter à {threshold}\u{a0}ans."),
+            ("registration-vote", "Vous avez {age} {age|plural:an:ans} et pouvez voter."),
+            ("greeting-morning", "Bonjour, {name}\u{202f}!"),
+            ("greeting-afternoon", "Bon après-midi, {name}\u{202f}!"),
+            ("greeting-evening", "Bonsoir, {name}\u{202f}!"),
+            ("greeting-new-year", "Bonne année, {name}\u{202f}!"),
+            ("greeting-birthday", "Joyeux anniversaire pour vos {age} ans, {name}\u{202f}!"),
+            ("greeting-birthday-vote", "Joyeux anniversaire pour vos {age} ans, {name}\u{202f}! Vous pouvez désormais voter."),
+        ],
+    ),
+    ("he", &[("greeting", "שלום, {name}!")]),
+    (
+        "hi",
+        &[
+            ("greeting", "नमस्ते, {name}!"),
+            ("eligible", "आपकी आयु {age} वर्ष है, आप मतदान कर सकते हैं।"),
+            ("not-eligible", "आपकी आयु {age} वर्ष है, आप अभी मतदान नहीं कर सकते (न्यूनतम आयु: {threshold})।"),
+            ("just-eligible", "बधाई हो! आपको मतदान का अधिकार मिल गया है।"),
+            ("years-until-eligible.other", "आप {years} वर्ष बाद मतदान कर सकेंगे।"),
+        ],
+    ),
+    ("hu", &[("greeting", "Szia, {name}!")]),
+    ("id", &[("greeting", "Halo, {name}!")]),
+    (
+        "it",
+        &[
+            ("greeting", "Ciao, {name}!"),
+            ("eligible", "Hai {age} anni: puoi votare."),
+            ("not-eligible.one", "Hai {age} anno: non puoi ancora votare (età minima: {threshold})."),
+            ("not-eligible.other", "Hai {age} anni: non puoi ancora votare (età minima: {threshold})."),
+            ("just-eligible", "Congratulazioni! Hai acquisito il diritto di voto."),
+            ("years-until-eligible.one", "Potrai votare tra {years} anno, al compimento dei {threshold} anni."),
+            ("years-until-eligible.other", "Potrai votare tra {years} anni, al compimento dei {threshold} anni."),
+            ("greeting-morning", "Buongiorno, {name}!"),
+            ("greeting-afternoon", "Buon pomeriggio, {name}!"),
+            ("greeting-evening", "Buonasera, {name}!"),
+            ("greeting-new-year", "Buon anno, {name}!"),
+        ],
+    ),
+    (
+        "ja",
+        &[
+            ("greeting", "こんにちは、{name}さん！"),
+            ("eligible", "{age}歳なので投票できます。"),
+            ("not-eligible", "{age}歳なのでまだ投票できません（選挙権年齢：{threshold}歳）。"),
+            ("just-eligible", "おめでとうございます！選挙権を得ました。"),
+            ("years-until-eligible.other", "あと{years}年で投票できます。"),
+            ("greeting-morning", "おはようございます、{name}さん！"),
+            ("greeting-afternoon", "こんにちは、{name}さん！"),
+            ("greeting-evening", "こんばんは、{name}さん！"),
+            ("greeting-new-year", "あけましておめでとうございます、{name}さん！"),
+            ("greeting-birthday", "{name}さん、{age}歳のお誕生日おめでとうございます！"),
+            ("greeting-birthday-vote", "{name}さん、{age}歳のお誕生日おめでとうございます！これで投票できます。"),
+        ],
+    ),
+    (
+        "ko",
+        &[
+            ("greeting", "안녕하세요, {name}님!"),
+            ("eligible", "{age}세이므로 투표할 수 있습니다."),
+            ("not-eligible", "{age}세이므로 아직 투표할 수 없습니다(선거 연령: {threshold}세)."),
+            ("just-eligible", "축하합니다! 투표권을 얻었습니다."),
+            ("years-until-eligible.other", "{years}년 후에 투표할 수 있습니다."),
+        ],
+    ),
+    ("nb", &[("greeting", "Hei, {name}!")]),
+    (
+        "nl",
+        &[
+            ("greeting", "Hallo, {name}!"),
+            ("eligible", "Je bent {age} jaar en mag stemmen."),
+            ("not-eligible", "Je bent {age} jaar en mag nog niet stemmen (minimumleeftijd: {threshold})."),
+            ("just-eligible", "Gefeliciteerd! Je hebt stemrecht gekregen."),
+            ("years-until-eligible.other", "Je mag over {years} jaar stemmen, vanaf je {threshold|ordinal} verjaardag."),
+        ],
+    ),
+    (
+        "pl",
+        &[
+            ("greeting", "Cześć, {name}!"),
+            ("eligible.one", "Masz {age} rok — możesz głosować."),
+            ("eligible.few", "Masz {age} lata — możesz głosować."),
+            ("eligible.many", "Masz {age} lat — możesz głosować."),
+            ("not-eligible.one", "Masz {age} rok — nie możesz jeszcze głosować (minimalny wiek: {threshold})."),
+            ("not-eligible.few", "Masz {age} lata — nie możesz jeszcze głosować (minimalny wiek: {threshold})."),
+            ("not-eligible.many", "Masz {age} lat — nie możesz jeszcze głosować (minimalny wiek: {threshold})."),
+            ("just-eligible", "Gratulacje! Masz już prawo głosu."),
+            ("years-until-eligible.one", "Będziesz mógł głosować za {years} rok."),
+            ("years-until-eligible.few", "Będziesz mógł głosować za {years} lata."),
+            ("years-until-eligible.many", "Będziesz mógł głosować za {years} lat."),
+        ],
+    ),
+    (
+        "pt",
+        &[
+            ("greeting", "Olá, {name}!"),
+            ("eligible", "Você tem {age} anos: pode votar."),
+            ("not-eligible.one", "Você tem {age} ano: ainda não pode votar (idade mínima: {threshold})."),
+            ("not-eligible.other", "Você tem {age} anos: ainda não pode votar (idade mínima: {threshold})."),
+            ("just-eligible", "Parabéns! Você conquistou o direito de votar."),
+            ("years-until-eligible.one", "Você poderá votar daqui a {years} ano, aos {threshold} anos."),
+            ("years-until-eligible.other", "Você poderá votar daqui a {years} anos, aos {threshold} anos."),
+            ("countdown", "Você poderá votar a partir de {date}, daqui a {#if years}{years} {years|plural:ano:anos}{#if months}{#if days}, {#else} e {/if}{#else}{#if days} e {/if}{/if}{/if}{#if months}{months} {months|plural:mês:meses}{#if days} e {/if}{/if}{#if days}{days} {days|plural:dia:dias}{/if}.{#if election} A primeira eleição em que você poderá votar é em {election}.{/if}"),
+            ("countdown-eligible", "Você já pode votar.{#if election} A próxima eleição é em {election}.{/if}"),
+            ("registration-ineligible", "Você tem {age} {age|plural:ano:anos} e ainda não pode se registrar para votar."),
+            ("registration-preregister", "Você tem {age} {age|plural:ano:anos} e pode fazer o pré-registro eleitoral; poderá votar aos {threshold}."),
+            ("registration-register", "Você tem {age} {age|plural:ano:anos} e pode se registrar para votar; poderá votar aos {threshold}."),
+            ("registration-vote", "Você tem {age} {age|plural:ano:anos} e pode votar."),
+            ("greeting-morning", "Bom dia, {name}!"),
+            ("greeting-afternoon", "Boa tarde, {name}!"),
+            ("greeting-evening", "Boa noite, {name}!"),
+            ("greeting-new-year", "Feliz Ano Novo, {name}!"),
+            ("greeting-birthday", "Feliz aniversário de {age} anos, {name}!"),
+            ("greeting-birthday-vote", "Feliz aniversário de {age} anos, {name}! Agora você pode votar."),
+        ],
+    ),
+    ("pt-BR", &[("greeting", "Oi, {name}!")]),
+    ("ro", &[("greeting", "Salut, {name}!")]),
+    (
+        "ru",
+        &[
+            ("greeting", "Привет, {name}!"),
+            ("eligible.one", "Вам {age} год — вы можете голосовать."),
+            ("eligible.few", "Вам {age} года — вы можете голосовать."),
+            ("eligible.many", "Вам {age} лет — вы можете голосовать."),
+            ("not-eligible.one", "Вам {age} год — вы пока не можете голосовать (минимальный возраст: {threshold})."),
+            ("not-eligible.few", "Вам {age} года — вы пока не можете голосовать (минимальный возраст: {threshold})."),
+            ("not-eligible.many", "Вам {age} лет — вы пока не можете голосовать (минимальный возраст: {threshold})."),
+            ("just-eligible", "Поздравляем! Вы получили право голоса."),
+            ("years-until-eligible.one", "Вы сможете голосовать через {years} год."),
+            ("years-until-eligible.few", "Вы сможете голосовать через {years} года."),
+            ("years-until-eligible.many", "Вы сможете голосовать через {years} лет."),
+        ],
+    ),
+    (
+        "sv",
+        &[
+            ("greeting", "Hej, {name}!"),
+            ("eligible", "Du är {age} år och får rösta."),
+            ("not-eligible", "Du är {age} år och får inte rösta än (rösträttsålder: {threshold})."),
+            ("just-eligible", "Grattis! Du har fått rösträtt."),
+            ("years-until-eligible.other", "Du får rösta om {years} år, från din {threshold|ordinal} födelsedag."),
+        ],
+    ),
+    ("sw", &[("greeting", "Habari, {name}!")]),
+    ("th", &[("greeting", "สวัสดี {name}!")]),
+    (
+        "tr",
+        &[
+            ("greeting", "Merhaba, {name}!"),
+            ("eligible", "{age} yaşındasınız, oy kullanabilirsiniz."),
+            ("not-eligible", "{age} yaşındasınız, henüz oy kullanamazsınız (asgari yaş: {threshold})."),
+            ("just-eligible", "Tebrikler! Oy kullanma hakkı kazandınız."),
+            ("years-until-eligible.other", "{years} yıl sonra oy kullanabileceksiniz."),
+        ],
+    ),
+    (
+        "uk",
+        &[
+            ("greeting", "Привіт, {name}!"),
+            ("eligible.one", "Вам {age} рік — ви можете голосувати."),
+            ("eligible.few", "Вам {age} роки — ви можете голосувати."),
+            ("eligible.many", "Вам {age} років — ви можете голосувати."),
+            ("not-eligible.one", "Вам {age} рік — ви ще не можете голосувати (мінімальний вік: {threshold})."),
+            ("not-eligible.few", "Вам {age} роки — ви ще не можете голосувати (мінімальний вік: {threshold})."),
+            ("not-eligible.many", "Вам {age} років — ви ще не можете голосувати (мінімальний вік: {threshold})."),
+            ("just-eligible", "Вітаємо! Ви отримали право голосу."),
+            ("years-until-eligible.one", "Ви зможете голосувати через {years} рік."),
+            ("years-until-eligible.few", "Ви зможете голосувати через {years} роки."),
+            ("years-until-eligible.many", "Ви зможете голосувати через {years} років."),
+        ],
+    ),
+    ("vi", &[("greeting", "Xin chào, {name}!")]),
+    (
+        "zh",
+        &[
+            ("greeting", "你好，{name}！"),
+            ("eligible", "您{age}岁，可以投票。"),
+            ("not-eligible", "您{age}岁，暂时还不能投票（最低投票年龄：{threshold}岁）。"),
+            ("just-eligible", "恭喜！您已获得投票权。"),
+            ("years-until-eligible.other", "您还需{years}年才能投票。"),
+        ],
+    ),
+    (
+        "zh-Hant",
+        &[
+            ("greeting", "你好，{name}！"),
+            ("eligible", "您{age}歲，可以投票。"),
+            ("not-eligible", "您{age}歲，暫時還不能投票（最低投票年齡：{threshold}歲）。"),
+            ("just-eligible", "恭喜！您已取得投票權。"),
+            ("years-until-eligible.other", "您還需{years}年才能投票。"),
+        ],
+    ),
+];
+
+/// Locales with a built-in catalog, loaded Fluent messages or registered templates, sorted.
+pub fn locales() -> Vec<String> {
+    let mut locales: Vec<String> = CATALOGS
+        .iter()
+        .map(|(locale, _)| locale.to_string())
+        .chain(fluent::loaded_locales())
+        .chain(template::registered_locales())
+        .collect();
+    locales.sort();
+    locales.dedup();
+    locales
+}
+
+/// Locales that translate message `id` rather than falling back to English, sorted.
+///
+/// A locale qualifies through any less specific locale on its fallback chain, so `pt-BR`
+/// has every message `pt` has.
+pub fn locales_with(id: &str) -> Vec<String> {
+    let translated = |locale: &str| {
+        template::registered(locale, id).is_some()
+            || fluent::has_message(locale, id)
+            || CATALOGS
+                .iter()
+                .filter(|(candidate, _)| *candidate == locale)
+                .flat_map(|(_, messages)| messages.iter())
+                .any(|(key, _)| {
+                    key.strip_prefix(id)
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+                })
+    };
+    locales()
+        .into_iter()
+        .filter(|locale| {
+            locale::fallback_chain(locale)
+                .iter()
+                .filter(|candidate| *candidate == locale || *candidate != DEFAULT_LOCALE)
+                .any(|candidate| translated(candidate))
+        })
+        .collect()
+}
+
+fn builtin(locale: &str, id: &str) -> Option<&'static str> {
+    CATALOGS
+        .iter()
+        .find(|(candidate, _)| *candidate == locale)
+        .and_then(|(_, messages)| messages.iter().find(|(key, _)| *key == id))
+        .map(|(_, pattern)| *pattern)
+}
+
+/// A value substituted into a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arg<'a> {
+    Text(&'a str),
+    /// Written in the locale's digits and used to pick plural variants.
+    Number(i64),
+}
+
+/// The built-in message `id` of exactly `locale`, in the plural variant that agrees with `count`.
+fn builtin_variant(locale: &str, id: &str, count: Option<i64>) -> Option<&'static str> {
+    count
+        .and_then(|count| {
+            let category = plural::category(count, locale);
+            builtin(locale, &format!("{}.{}", id, category.as_str()))
+        })
+        .or_else(|| builtin(locale, &format!("{}.other", id)))
+        .or_else(|| builtin(locale, id))
+}
+
+/// Placeholders the built-in message `id` is rendered with, or `None` for unknown ids.
+pub fn placeholders(id: &str) -> Option<&'static [&'static str]> {
+    match id {
+        "greeting-birthday" | "greeting-birthday-vote" => Some(&["name", "age"]),
+        _ if id.starts_with("greeting") && builtin(DEFAULT_LOCALE, id).is_some() => Some(&["name"]),
+        "eligible" | "not-eligible" | "just-eligible" => Some(&["age", "threshold"]),
+        "years-until-eligible" => Some(&["years", "threshold"]),
+        "registration-ineligible"
+        | "registration-preregister"
+        | "registration-register"
+        | "registration-vote" => Some(&["age", "threshold"]),
+        "linked-election-eligible" | "linked-election-not-eligible" => {
+            Some(&["age", "threshold", "linkedAge", "date"])
+        }
+        "countdown" | "countdown-eligible" => {
+            Some(&["date", "years", "months", "days", "election"])
+        }
+        "trace-rule" | "trace-conviction-rule" => {
+            Some(&["rule", "jurisdiction", "threshold", "from", "to"])
+        }
+        "trace-age-less" | "trace-age-equal" | "trace-age-greater" => Some(&["age", "threshold"]),
+        _ if id.starts_with("trace-date-") || id.starts_with("trace-linked-") => {
+            Some(&["date", "birthday", "threshold"])
+        }
+        "trace-conditions-unmet" => Some(&["conditions"]),
+        "trace-record-allowed"
+        | "trace-record-barred"
+        | "trace-verdict-eligible"
+        | "trace-verdict-ineligible" => Some(&[]),
+        _ => None,
+    }
+}
+
+/// Renders message `id` in `locale`, walking its fallback chain.
+///
+/// At each step, a template registered through `Templates.register` wins, then messages
+/// loaded through `Messages.load`, then the built-in catalog, so a locale's own plural forms
+/// win over a less specific locale's catch-all and anything missing everywhere falls back to
+/// the built-in English text.
+pub fn render(locale: &str, id: &str, args: &[(&str, Arg)], count: Option<i64>) -> String {
+    render_with(locale, id, args, count, &|text| text.to_string())
+}
+
+/// Like [`render`], passing every substituted value through `escape`.
+pub fn render_with(
+    locale: &str,
+    id: &str,
+    args: &[(&str, Arg)],
+    count: Option<i64>,
+    escape: &dyn Fn(&str) -> String,
+) -> String {
+    for candidate in locale::fallback_chain(locale) {
+        if let Some(registered) = template::registered(&candidate, id) {
+            return registered.render(args, locale, escape);
+        }
+        if let Some(text) = fluent::render(&candidate, id, args, locale, escape) {
+            return text;
+        }
+        if let Some(pattern) = builtin_variant(&candidate, id, count) {
+            return template::builtin(pattern).render(args, locale, escape);
+        }
+    }
+    id.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eligibility_locales_leave_out_greeting_only_catalogs() {
+        let translated = locales_with("eligible");
+        for locale in ["en", "pl", "pt-BR", "zh-Hant"] {
+            assert!(
+                translated.iter().any(|l| l == locale),
+                "{} is missing",
+                locale
+            );
+        }
+        for locale in ["da", "el", "fi", "vi"] {
+            assert!(
+                !translated.iter().any(|l| l == locale),
+                "{} is listed",
+                locale
+            );
+        }
+        assert!(locales_with("greeting").iter().any(|l| l == "da"));
+        assert_eq!(locales_with("greeting").len(), locales().len());
+    }
+
+    #[test]
+    fn loaded_fluent_messages_count_as_translations() {
+        fluent::load("da", "eligible = Du er { $age } år og må stemme.").unwrap();
+        assert!(locales_with("eligible").iter().any(|l| l == "da"));
+        fluent::clear(Some("da"));
+    }
+
+    #[test]
+    fn renders_the_plural_variant_for_the_count() {
+        let render_ru = |age| {
+            render(
+                "ru",
+                "not-eligible",
+                &[("age", Arg::Number(age)), ("threshold", Arg::Number(18))],
+                Some(age),
+            )
+        };
+        assert!(render_ru(1).starts_with("Вам 1 год"));
+        assert!(render_ru(3).starts_with("Вам 3 года"));
+        assert!(render_ru(17).starts_with("Вам 17 лет"));
+    }
+
+    #[test]
+    fn falls_back_along_the_chain_to_english() {
+        let args = [("name", Arg::Text("Ana"))];
+        assert_eq!(render("pt-BR", "greeting", &args, None), "Oi, Ana!");
+        assert_eq!(
+            render("pt-BR", "greeting-morning", &args, None),
+            "Bom dia, Ana!"
+        );
+        assert_eq!(
+            render("da", "greeting-morning", &args, None),
+            "Good morning, Ana!"
+        );
+        assert_eq!(
+            render("xx", "no-such-message", &args, None),
+            "no-such-message"
+        );
+    }
+}
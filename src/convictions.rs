@@ -0,0 +1,602 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use chrono::{Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::dates;
+use crate::error::EligibilityError;
+use crate::js;
+use crate::rules;
+
+/// Conviction rules shipped with the crate; `ConvictionRules.load` replaces them at runtime.
+const BUILTIN_RULES: &str = include_str!("../data/conviction_rules.json");
+
+thread_local! {
+    /// The conviction rules in force, parsed from [`BUILTIN_RULES`] on first use.
+    static RULES: RefCell<Option<RuleSet>> = const { RefCell::new(None) };
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const VOTING_RIGHTS_JSON: &'static str = r#"
+export interface Conviction {
+  felony?: boolean;
+  convictedOn?: string;
+  status?: "incarcerated" | "parole" | "probation" | "completed";
+  releaseDate?: string;
+  sentenceEnds?: string;
+  rightsRestored?: boolean;
+}
+
+export interface VotingRightsJSON {
+  status: "Retained" | "Suspended" | "Restored" | "Lost";
+  policy: "NeverLost" | "WhileIncarcerated" | "UntilSentenceComplete" | "PermanentUnlessRestored" | null;
+  restorationDate: string | null;
+  reason: string;
+  jurisdiction: string;
+  rulesVersion: string;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "VotingRightsJSON")]
+    pub type VotingRightsJson;
+}
+
+/// When a felony conviction takes away the vote, and when it comes back.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all(deserialize = "kebab-case"))]
+pub enum DisenfranchisementPolicy {
+    /// Convictions never affect the vote.
+    NeverLost,
+    /// The vote is lost only while in prison.
+    WhileIncarcerated,
+    /// The vote returns once the whole sentence, including parole and probation, is served.
+    UntilSentenceComplete,
+    /// The vote is lost for good unless restored by petition, pardon or clemency.
+    PermanentUnlessRestored,
+}
+
+/// Where a sentence stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SentenceStatus {
+    Incarcerated,
+    Parole,
+    Probation,
+    #[default]
+    Completed,
+}
+
+/// One conviction, as supplied by the caller.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Conviction {
+    /// Misdemeanors never affect the vote.
+    #[serde(default = "felony_default")]
+    pub felony: bool,
+    /// ISO-8601 date of the conviction; a conviction after the reference date is ignored.
+    #[serde(default)]
+    pub convicted_on: Option<String>,
+    #[serde(default)]
+    pub status: SentenceStatus,
+    /// ISO-8601 date of release from prison, past or expected.
+    #[serde(default)]
+    pub release_date: Option<String>,
+    /// ISO-8601 date the whole sentence ends, past or expected.
+    #[serde(default)]
+    pub sentence_ends: Option<String>,
+    /// Rights were restored by petition, pardon or clemency.
+    #[serde(default)]
+    pub rights_restored: bool,
+}
+
+fn felony_default() -> bool {
+    true
+}
+
+/// A jurisdiction's entry in the conviction rules.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PolicySpec {
+    pub policy: DisenfranchisementPolicy,
+    /// Years after the sentence ends before the vote returns.
+    #[serde(default)]
+    pub waiting_years: u8,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// A complete set of conviction rules, as loaded from JSON.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleSet {
+    pub version: String,
+    pub jurisdictions: HashMap<String, PolicySpec>,
+}
+
+impl RuleSet {
+    /// Parses rules from JSON, normalizing jurisdiction codes.
+    pub fn from_json(text: &str) -> Result<Self, EligibilityError> {
+        let mut rules: RuleSet = serde_json::from_str(text)?;
+        rules.jurisdictions = rules
+            .jurisdictions
+            .into_iter()
+            .map(|(code, spec)| (rules::normalize_jurisdiction(&code), spec))
+            .collect();
+        Ok(rules)
+    }
+
+    /// The entry for `jurisdiction`, falling back from a subdivision to its country.
+    pub fn lookup(&self, jurisdiction: &str) -> Option<&PolicySpec> {
+        let code = rules::normalize_jurisdiction(jurisdiction);
+        let country = code.split('-').next().unwrap_or_default();
+        self.jurisdictions
+            .get(&code)
+            .or_else(|| self.jurisdictions.get(country))
+    }
+}
+
+fn with_rules<T>(f: impl FnOnce(&RuleSet) -> T) -> T {
+    RULES.with(|rules| {
+        let mut rules = rules.borrow_mut();
+        let rules = rules.get_or_insert_with(|| {
+            RuleSet::from_json(BUILTIN_RULES).expect("the built-in conviction rules are valid")
+        });
+        f(rules)
+    })
+}
+
+/// Replaces the conviction rules in force.
+pub fn load(text: &str) -> Result<(), EligibilityError> {
+    let rules = RuleSet::from_json(text)?;
+    RULES.with(|current| *current.borrow_mut() = Some(rules));
+    Ok(())
+}
+
+/// Goes back to the built-in conviction rules.
+pub fn reset() {
+    RULES.with(|current| *current.borrow_mut() = None);
+}
+
+/// The policy in force for `jurisdiction`, if the rules cover it.
+pub fn policy_for(jurisdiction: &str) -> Option<PolicySpec> {
+    with_rules(|rules| rules.lookup(jurisdiction).cloned())
+}
+
+/// Whether someone may vote given their criminal record.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum RightsStatus {
+    /// Never affected, either by the record or by the jurisdiction's policy.
+    Retained,
+    /// Lost for now; see the restoration date.
+    Suspended,
+    /// Lost once and since restored.
+    Restored,
+    /// Lost until restored by petition, pardon or clemency.
+    Lost,
+}
+
+/// Current voting rights under a jurisdiction's conviction policy.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VotingRights {
+    status: RightsStatus,
+    policy: Option<DisenfranchisementPolicy>,
+    #[serde(serialize_with = "dates::serialize_iso_opt")]
+    restoration_date: Option<NaiveDate>,
+    reason: String,
+    jurisdiction: String,
+    rules_version: String,
+}
+
+impl VotingRights {
+    /// Whether the record allows voting now.
+    pub fn can_vote(&self) -> bool {
+        matches!(self.status, RightsStatus::Retained | RightsStatus::Restored)
+    }
+
+    pub fn restoration(&self) -> Option<NaiveDate> {
+        self.restoration_date
+    }
+}
+
+#[wasm_bindgen]
+impl VotingRights {
+    #[wasm_bindgen(getter)]
+    pub fn status(&self) -> RightsStatus {
+        self.status
+    }
+
+    #[wasm_bindgen(getter, js_name = canVote)]
+    pub fn js_can_vote(&self) -> bool {
+        self.can_vote()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn policy(&self) -> Option<DisenfranchisementPolicy> {
+        self.policy
+    }
+
+    /// The day the vote returns, as `YYYY-MM-DD`, when it is suspended and the date is known.
+    #[wasm_bindgen(getter, js_name = restorationDate)]
+    pub fn restoration_date(&self) -> Option<String> {
+        self.restoration_date.map(|date| date.to_string())
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn reason(&self) -> String {
+        self.reason.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn jurisdiction(&self) -> String {
+        self.jurisdiction.clone()
+    }
+
+    /// Version of the rules the decision was made under.
+    #[wasm_bindgen(getter, js_name = rulesVersion)]
+    pub fn rules_version(&self) -> String {
+        self.rules_version.clone()
+    }
+
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json(&self) -> Result<VotingRightsJson, JsError> {
+        Ok(js::to_js(self)?.unchecked_into())
+    }
+}
+
+fn optional_date(text: &Option<String>) -> Result<Option<NaiveDate>, EligibilityError> {
+    text.as_deref().map(dates::parse_iso_date).transpose()
+}
+
+/// The status one conviction leaves, with the restoration date where one applies.
+fn judge(
+    conviction: &Conviction,
+    spec: &PolicySpec,
+    on: NaiveDate,
+) -> Result<(RightsStatus, Option<NaiveDate>), EligibilityError> {
+    if !conviction.felony {
+        return Ok((RightsStatus::Retained, None));
+    }
+    // A conviction after the reference date had not happened yet.
+    if optional_date(&conviction.convicted_on)?.is_some_and(|convicted| convicted > on) {
+        return Ok((RightsStatus::Retained, None));
+    }
+    let release = optional_date(&conviction.release_date)?;
+    let sentence_ends = optional_date(&conviction.sentence_ends)?;
+    Ok(match spec.policy {
+        DisenfranchisementPolicy::NeverLost => (RightsStatus::Retained, None),
+        DisenfranchisementPolicy::WhileIncarcerated => {
+            let incarcerated = conviction.status == SentenceStatus::Incarcerated
+                && release.is_none_or(|release| release > on);
+            if incarcerated {
+                (RightsStatus::Suspended, release)
+            } else if release.is_some_and(|release| release <= on) {
+                (RightsStatus::Restored, None)
+            } else {
+                // Never in prison, so the vote was never lost.
+                (RightsStatus::Retained, None)
+            }
+        }
+        DisenfranchisementPolicy::UntilSentenceComplete if conviction.rights_restored => {
+            (RightsStatus::Restored, None)
+        }
+        DisenfranchisementPolicy::UntilSentenceComplete => {
+            let restores = sentence_ends
+                .map(|end| {
+                    end.checked_add_months(Months::new(u32::from(spec.waiting_years) * 12))
+                        .ok_or(EligibilityError::DateOutOfRange {
+                            date: end,
+                            years: spec.waiting_years.into(),
+                        })
+                })
+                .transpose()?;
+            let served = match (conviction.status, restores) {
+                (_, Some(restores)) => restores <= on,
+                (SentenceStatus::Completed, None) => spec.waiting_years == 0,
+                (_, None) => false,
+            };
+            if served {
+                (RightsStatus::Restored, None)
+            } else {
+                (RightsStatus::Suspended, restores)
+            }
+        }
+        DisenfranchisementPolicy::PermanentUnlessRestored if conviction.rights_restored => {
+            (RightsStatus::Restored, None)
+        }
+        DisenfranchisementPolicy::PermanentUnlessRestored => (RightsStatus::Lost, None),
+    })
+}
+
+/// Voting rights in `jurisdiction` on `on` for someone with `convictions`.
+///
+/// Several convictions combine to the worst status; a suspension only has a restoration
+/// date when every suspending conviction has one.
+pub fn voting_rights(
+    jurisdiction: &str,
+    convictions: &[Conviction],
+    on: NaiveDate,
+) -> Result<VotingRights, EligibilityError> {
+    let code = rules::normalize_jurisdiction(jurisdiction);
+    let (spec, version) = with_rules(|rules| (rules.lookup(&code).cloned(), rules.version.clone()));
+    let rights = |status, policy, restoration_date, reason: String| VotingRights {
+        status,
+        policy,
+        restoration_date,
+        reason,
+        jurisdiction: code.clone(),
+        rules_version: version.clone(),
+    };
+    if !convictions.iter().any(|conviction| conviction.felony) {
+        let policy = spec.map(|spec| spec.policy);
+        return Ok(rights(
+            RightsStatus::Retained,
+            policy,
+            None,
+            "No felony convictions.".to_string(),
+        ));
+    }
+    let spec = spec.ok_or_else(|| EligibilityError::UnknownJurisdiction(code.clone()))?;
+
+    let mut status = RightsStatus::Retained;
+    let mut restoration: Option<Option<NaiveDate>> = None;
+    for conviction in convictions {
+        let (judged, date) = judge(conviction, &spec, on)?;
+        if judged == RightsStatus::Suspended {
+            restoration = Some(match restoration {
+                None => date,
+                Some(current) => current.zip(date).map(|(a, b)| a.max(b)),
+            });
+        }
+        status = status.max(judged);
+    }
+    let restoration = match status {
+        RightsStatus::Suspended => restoration.flatten(),
+        _ => None,
+    };
+    let mut reason = match (status, spec.policy) {
+        (RightsStatus::Retained, _) | (_, DisenfranchisementPolicy::NeverLost) => {
+            "Convictions do not affect the vote here.".to_string()
+        }
+        (RightsStatus::Suspended, DisenfranchisementPolicy::WhileIncarcerated) => {
+            "The vote is suspended while in prison.".to_string()
+        }
+        (RightsStatus::Suspended, _) if spec.waiting_years > 0 => format!(
+            "The vote is suspended until {} years after the sentence is complete.",
+            spec.waiting_years
+        ),
+        (RightsStatus::Suspended, _) => {
+            "The vote is suspended until the sentence is complete.".to_string()
+        }
+        (RightsStatus::Restored, _) => "The vote has been restored.".to_string(),
+        (RightsStatus::Lost, _) => {
+            "The vote is lost unless restored by petition, pardon or clemency.".to_string()
+        }
+    };
+    if let Some(note) = &spec.note {
+        reason = format!("{} {}", reason, note);
+    }
+    Ok(rights(status, Some(spec.policy), restoration, reason))
+}
+
+/// The conviction rules in force, loadable from JSON so they can change without a release.
+#[wasm_bindgen]
+pub struct ConvictionRules;
+
+#[wasm_bindgen]
+impl ConvictionRules {
+    /// Replaces the rules with `{ "version": "...", "jurisdictions": { "US-TX": { "policy":
+    /// "until-sentence-complete", "waitingYears": 0 } } }`.
+    pub fn load(json: &str) -> Result<(), JsError> {
+        Ok(load(json)?)
+    }
+
+    /// Goes back to the rules shipped with the package.
+    pub fn reset() {
+        reset()
+    }
+
+    /// Version string of the rules in force.
+    pub fn version() -> String {
+        with_rules(|rules| rules.version.clone())
+    }
+
+    /// The policy for `jurisdiction`, falling back from a subdivision to its country.
+    pub fn policy(jurisdiction: &str) -> Option<DisenfranchisementPolicy> {
+        policy_for(jurisdiction).map(|spec| spec.policy)
+    }
+}
+
+/// Voting rights in `jurisdiction` on `on` given an array of convictions.
+#[wasm_bindgen]
+pub fn check_voting_rights(
+    jurisdiction: &str,
+    convictions: JsValue,
+    on: JsValue,
+) -> Result<VotingRights, JsError> {
+    let convictions: Vec<Conviction> = js::options_from_js(convictions)?;
+    Ok(voting_rights(
+        jurisdiction,
+        &convictions,
+        dates::date_from_js(&on)?,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(text: &str) -> NaiveDate {
+        dates::parse_iso_date(text).unwrap()
+    }
+
+    fn conviction(json: &str) -> Conviction {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn spec(policy: DisenfranchisementPolicy, waiting_years: u8) -> PolicySpec {
+        PolicySpec {
+            policy,
+            waiting_years,
+            note: None,
+        }
+    }
+
+    #[test]
+    fn never_lost_retains_the_vote_in_prison() {
+        let jailed = conviction(r#"{"status": "incarcerated"}"#);
+        let spec = spec(DisenfranchisementPolicy::NeverLost, 0);
+        assert_eq!(
+            judge(&jailed, &spec, date("2026-01-01")).unwrap(),
+            (RightsStatus::Retained, None)
+        );
+    }
+
+    #[test]
+    fn while_incarcerated_suspends_until_release() {
+        let jailed = conviction(r#"{"status": "incarcerated", "releaseDate": "2027-03-01"}"#);
+        let spec = spec(DisenfranchisementPolicy::WhileIncarcerated, 0);
+        assert_eq!(
+            judge(&jailed, &spec, date("2026-01-01")).unwrap(),
+            (RightsStatus::Suspended, Some(date("2027-03-01")))
+        );
+        assert_eq!(
+            judge(&jailed, &spec, date("2027-03-01")).unwrap(),
+            (RightsStatus::Restored, None)
+        );
+        let paroled = conviction(r#"{"status": "parole", "releaseDate": "2025-06-01"}"#);
+        assert_eq!(
+            judge(&paroled, &spec, date("2026-01-01")).unwrap(),
+            (RightsStatus::Restored, None)
+        );
+    }
+
+    #[test]
+    fn while_incarcerated_retains_the_vote_of_those_never_in_prison() {
+        let spec = spec(DisenfranchisementPolicy::WhileIncarcerated, 0);
+        let probation = conviction(r#"{"status": "probation"}"#);
+        assert_eq!(
+            judge(&probation, &spec, date("2026-01-01")).unwrap(),
+            (RightsStatus::Retained, None)
+        );
+    }
+
+    #[test]
+    fn until_sentence_complete_counts_the_waiting_period() {
+        let paroled = conviction(r#"{"status": "parole", "sentenceEnds": "2026-06-30"}"#);
+        let immediate = spec(DisenfranchisementPolicy::UntilSentenceComplete, 0);
+        assert_eq!(
+            judge(&paroled, &immediate, date("2026-01-01")).unwrap(),
+            (RightsStatus::Suspended, Some(date("2026-06-30")))
+        );
+        assert_eq!(
+            judge(&paroled, &immediate, date("2026-06-30")).unwrap(),
+            (RightsStatus::Restored, None)
+        );
+        let waiting = spec(DisenfranchisementPolicy::UntilSentenceComplete, 5);
+        assert_eq!(
+            judge(&paroled, &waiting, date("2030-01-01")).unwrap(),
+            (RightsStatus::Suspended, Some(date("2031-06-30")))
+        );
+        let undated = conviction(r#"{"status": "completed"}"#);
+        assert_eq!(
+            judge(&undated, &waiting, date("2030-01-01")).unwrap(),
+            (RightsStatus::Suspended, None)
+        );
+    }
+
+    #[test]
+    fn until_sentence_complete_honours_an_early_restoration() {
+        let pardoned = conviction(
+            r#"{"status": "parole", "sentenceEnds": "2030-01-01", "rightsRestored": true}"#,
+        );
+        let waiting = spec(DisenfranchisementPolicy::UntilSentenceComplete, 5);
+        assert_eq!(
+            judge(&pardoned, &waiting, date("2026-01-01")).unwrap(),
+            (RightsStatus::Restored, None)
+        );
+    }
+
+    #[test]
+    fn waiting_period_past_the_calendar_is_an_error() {
+        let paroled = conviction(r#"{"status": "parole", "sentenceEnds": "+262140-01-01"}"#);
+        let waiting = spec(DisenfranchisementPolicy::UntilSentenceComplete, 10);
+        assert!(matches!(
+            judge(&paroled, &waiting, date("2026-01-01")),
+            Err(EligibilityError::DateOutOfRange { years: 10, .. })
+        ));
+    }
+
+    #[test]
+    fn permanent_unless_restored_needs_a_restoration() {
+        let spec = spec(DisenfranchisementPolicy::PermanentUnlessRestored, 0);
+        let served = conviction(r#"{"status": "completed"}"#);
+        assert_eq!(
+            judge(&served, &spec, date("2026-01-01")).unwrap(),
+            (RightsStatus::Lost, None)
+        );
+        let pardoned = conviction(r#"{"rightsRestored": true}"#);
+        assert_eq!(
+            judge(&pardoned, &spec, date("2026-01-01")).unwrap(),
+            (RightsStatus::Restored, None)
+        );
+    }
+
+    #[test]
+    fn misdemeanors_and_later_convictions_are_ignored() {
+        let spec = spec(DisenfranchisementPolicy::PermanentUnlessRestored, 0);
+        let misdemeanor = conviction(r#"{"felony": false}"#);
+        assert_eq!(
+            judge(&misdemeanor, &spec, date("2026-01-01")).unwrap(),
+            (RightsStatus::Retained, None)
+        );
+        let later = conviction(r#"{"convictedOn": "2026-05-01"}"#);
+        assert_eq!(
+            judge(&later, &spec, date("2026-01-01")).unwrap(),
+            (RightsStatus::Retained, None)
+        );
+        assert_eq!(
+            judge(&later, &spec, date("2026-05-01")).unwrap(),
+            (RightsStatus::Lost, None)
+        );
+    }
+
+    #[test]
+    fn several_convictions_combine_to_the_worst_status() {
+        load(
+            r#"{"version": "test", "jurisdictions": {"XA": {"policy": "until-sentence-complete"}}}"#,
+        )
+        .unwrap();
+        let convictions = [
+            conviction(r#"{"status": "parole", "sentenceEnds": "2026-06-30"}"#),
+            conviction(r#"{"status": "probation", "sentenceEnds": "2027-02-01"}"#),
+            conviction(r#"{"felony": false}"#),
+        ];
+        let rights = voting_rights("xa", &convictions, date("2026-01-01")).unwrap();
+        assert_eq!(rights.status(), RightsStatus::Suspended);
+        assert_eq!(rights.restoration(), Some(date("2027-02-01")));
+        assert_eq!(rights.rules_version(), "test");
+        assert!(!rights.can_vote());
+        reset();
+    }
+
+    #[test]
+    fn subdivisions_fall_back_to_their_country() {
+        let rules = RuleSet::from_json(
+            r#"{"version": "1", "jurisdictions": {"us": {"policy": "never-lost"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            rules.lookup("US-ZZ").map(|spec| spec.policy),
+            Some(DisenfranchisementPolicy::NeverLost)
+        );
+        assert!(RuleSet::from_json(r#"{"version": "1", "jurisdictions": {}, "x": 1}"#).is_err());
+    }
+}
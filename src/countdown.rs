@@ -0,0 +1,333 @@
+use std::cmp::Ordering;
+
+use chrono::NaiveDate;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::catalog::{self, Arg};
+use crate::dates;
+use crate::eligibility::EligibilityStatus;
+use crate::error::EligibilityError;
+use crate::js;
+use crate::locale;
+use crate::rules::{self, VotingAgeRule};
+
+#[wasm_bindgen(typescript_custom_section)]
+const COUNTDOWN_JSON: &'static str = r#"
+export interface CountdownJSON {
+  status: "Eligible" | "NotYetEligible" | "JustEligible";
+  eligibleFrom: string;
+  reference: string;
+  years: number;
+  months: number;
+  days: number;
+  totalDays: number;
+  firstElection: string | null;
+  jurisdiction: string;
+  ruleId: string;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "CountdownJSON")]
+    pub type CountdownJson;
+}
+
+/// How long until someone can vote, and the first election they can vote in.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Countdown {
+    status: EligibilityStatus,
+    #[serde(serialize_with = "dates::serialize_iso")]
+    eligible_from: NaiveDate,
+    #[serde(serialize_with = "dates::serialize_iso")]
+    reference: NaiveDate,
+    years: u32,
+    months: u32,
+    days: u32,
+    total_days: u32,
+    #[serde(serialize_with = "dates::serialize_iso_opt")]
+    first_election: Option<NaiveDate>,
+    jurisdiction: String,
+    rule_id: String,
+}
+
+impl Countdown {
+    pub fn eligible_from_date(&self) -> NaiveDate {
+        self.eligible_from
+    }
+
+    pub fn first_election_date(&self) -> Option<NaiveDate> {
+        self.first_election
+    }
+
+    /// Remaining whole years, months and days.
+    pub fn remaining(&self) -> (u32, u32, u32) {
+        (self.years, self.months, self.days)
+    }
+}
+
+#[wasm_bindgen]
+impl Countdown {
+    #[wasm_bindgen(getter)]
+    pub fn status(&self) -> EligibilityStatus {
+        self.status
+    }
+
+    /// Whether the reference date is on or after the date eligibility begins.
+    #[wasm_bindgen(getter)]
+    pub fn eligible(&self) -> bool {
+        self.status != EligibilityStatus::NotYetEligible
+    }
+
+    /// The date eligibility begins, as `YYYY-MM-DD`.
+    #[wasm_bindgen(getter, js_name = eligibleFrom)]
+    pub fn eligible_from(&self) -> String {
+        self.eligible_from.to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn reference(&self) -> String {
+        self.reference.to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn years(&self) -> u32 {
+        self.years
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn months(&self) -> u32 {
+        self.months
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn days(&self) -> u32 {
+        self.days
+    }
+
+    #[wasm_bindgen(getter, js_name = totalDays)]
+    pub fn total_days(&self) -> u32 {
+        self.total_days
+    }
+
+    /// The first of the given elections held on or after eligibility begins, as `YYYY-MM-DD`.
+    #[wasm_bindgen(getter, js_name = firstElection)]
+    pub fn first_election(&self) -> Option<String> {
+        self.first_election.map(|date| date.to_string())
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn jurisdiction(&self) -> String {
+        self.jurisdiction.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = ruleId)]
+    pub fn rule_id(&self) -> String {
+        self.rule_id.clone()
+    }
+
+    /// "You can vote from 2027-03-14, in 1 year, 2 months and 3 days." in `locale`.
+    #[wasm_bindgen(js_name = localizedMessage)]
+    pub fn localized_message(&self, locale: &str) -> String {
+        let eligible_from = self.eligible_from.to_string();
+        let first_election = self.first_election.map(|date| date.to_string());
+        let mut args = vec![
+            ("date", Arg::Text(&eligible_from)),
+            ("years", Arg::Number(self.years.into())),
+            ("months", Arg::Number(self.months.into())),
+            ("days", Arg::Number(self.days.into())),
+        ];
+        if let Some(election) = &first_election {
+            args.push(("election", Arg::Text(election)));
+        }
+        let id = match self.status {
+            EligibilityStatus::NotYetEligible => "countdown",
+            _ => "countdown-eligible",
+        };
+        catalog::render(locale, id, &args, None)
+    }
+
+    /// `localizedMessage` in English.
+    #[wasm_bindgen(getter)]
+    pub fn message(&self) -> String {
+        self.localized_message(locale::DEFAULT_LOCALE)
+    }
+
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json(&self) -> Result<CountdownJson, JsError> {
+        Ok(js::to_js(self)?.unchecked_into())
+    }
+}
+
+/// Counts down from `reference` to the day someone born on `dob` reaches `rule`'s voting age.
+///
+/// `elections` need not be sorted; the earliest one on or after both the eligibility date
+/// and `reference` becomes the first election.
+pub fn countdown(
+    dob: NaiveDate,
+    reference: NaiveDate,
+    elections: &[NaiveDate],
+    rule: &VotingAgeRule,
+) -> Result<Countdown, EligibilityError> {
+    if reference < dob {
+        return Err(EligibilityError::BornAfterReference { dob, on: reference });
+    }
+    let eligible_from = dates::anniversary(dob, rule.voting_age().into(), rule.leap_day())?;
+    let (years, months, days, total_days) = match reference.cmp(&eligible_from) {
+        Ordering::Less => {
+            let (years, months, days) = dates::calendar_difference(reference, eligible_from);
+            let total = (eligible_from - reference).num_days() as u32;
+            (years, months, days, total)
+        }
+        _ => (0, 0, 0, 0),
+    };
+    let votes_from = eligible_from.max(reference);
+    Ok(Countdown {
+        status: reference.cmp(&eligible_from).into(),
+        eligible_from,
+        reference,
+        years,
+        months,
+        days,
+        total_days,
+        first_election: elections
+            .iter()
+            .copied()
+            .filter(|date| *date >= votes_from)
+            .min(),
+        jurisdiction: rule.jurisdiction(),
+        rule_id: rule.id(),
+    })
+}
+
+/// [`countdown`] against the voting age in force in `jurisdiction` on `reference`.
+pub fn countdown_in(
+    jurisdiction: &str,
+    dob: NaiveDate,
+    reference: NaiveDate,
+    elections: &[NaiveDate],
+) -> Result<Countdown, EligibilityError> {
+    countdown(
+        dob,
+        reference,
+        elections,
+        &rules::lookup_as_of(jurisdiction, reference)?,
+    )
+}
+
+/// Counts down to eligibility in `jurisdiction` from `reference`.
+///
+/// Dates may be ISO-8601 strings or JS `Date` objects; `elections` lists the scheduled
+/// election dates to pick the first votable one from.
+#[wasm_bindgen]
+pub fn eligibility_countdown(
+    jurisdiction: &str,
+    date_of_birth: JsValue,
+    reference: JsValue,
+    elections: Option<Vec<JsValue>>,
+) -> Result<Countdown, JsError> {
+    let dob = dates::date_from_js(&date_of_birth)?;
+    let reference = dates::date_from_js(&reference)?;
+    let elections = elections
+        .unwrap_or_default()
+        .iter()
+        .map(dates::date_from_js)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(countdown_in(jurisdiction, dob, reference, &elections)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(text: &str) -> NaiveDate {
+        dates::parse_iso_date(text).unwrap()
+    }
+
+    fn dates(texts: &[&str]) -> Vec<NaiveDate> {
+        texts.iter().map(|text| date(text)).collect()
+    }
+
+    #[test]
+    fn counts_down_in_calendar_units() {
+        let elections = dates(&["2028-11-07", "2026-11-03", "2027-05-06"]);
+        let countdown =
+            countdown_in("US", date("2009-03-14"), date("2026-01-11"), &elections).unwrap();
+        assert_eq!(countdown.status(), EligibilityStatus::NotYetEligible);
+        assert_eq!(countdown.eligible_from(), "2027-03-14");
+        assert_eq!(countdown.remaining(), (1, 2, 3));
+        assert_eq!(countdown.total_days(), 427);
+        assert_eq!(countdown.first_election().as_deref(), Some("2027-05-06"));
+        assert_eq!(
+            countdown.message(),
+            "You can vote from 2027-03-14, in 1 year, 2 months and 3 days. \
+             The first election you can vote in is on 2027-05-06."
+        );
+    }
+
+    #[test]
+    fn leaves_out_years_under_a_year_away() {
+        let countdown = countdown_in("US", date("2008-07-01"), date("2026-05-31"), &[]).unwrap();
+        assert_eq!(countdown.remaining(), (0, 1, 1));
+        assert_eq!(
+            countdown.message(),
+            "You can vote from 2026-07-01, in 1 month and 1 day."
+        );
+    }
+
+    #[test]
+    fn leaves_out_every_unit_that_is_zero() {
+        let years = countdown_in("US", date("2009-03-14"), date("2025-03-14"), &[]).unwrap();
+        assert_eq!(years.remaining(), (2, 0, 0));
+        assert_eq!(years.message(), "You can vote from 2027-03-14, in 2 years.");
+
+        let days = countdown_in("US", date("2008-01-21"), date("2026-01-11"), &[]).unwrap();
+        assert_eq!(days.remaining(), (0, 0, 10));
+        assert_eq!(days.message(), "You can vote from 2026-01-21, in 10 days.");
+
+        let both = countdown_in("US", date("2009-01-21"), date("2026-01-11"), &[]).unwrap();
+        assert_eq!(both.remaining(), (1, 0, 10));
+        assert_eq!(
+            both.localized_message("fr"),
+            "Vous pourrez voter à partir du 2027-01-21, dans 1 an et 10 jours."
+        );
+    }
+
+    #[test]
+    fn stops_at_zero_once_eligible() {
+        let elections = dates(&["2025-11-04", "2026-11-03"]);
+        let countdown =
+            countdown_in("US", date("2000-01-01"), date("2026-01-11"), &elections).unwrap();
+        assert!(countdown.eligible());
+        assert_eq!(countdown.remaining(), (0, 0, 0));
+        assert_eq!(countdown.first_election_date(), Some(date("2026-11-03")));
+        assert_eq!(
+            countdown.message(),
+            "You can already vote. The next election is on 2026-11-03."
+        );
+
+        let today = countdown_in("US", date("2008-01-11"), date("2026-01-11"), &[]).unwrap();
+        assert_eq!(today.status(), EligibilityStatus::JustEligible);
+    }
+
+    #[test]
+    fn uses_the_rule_in_force_on_the_reference_date() {
+        let dob = date("1998-01-01");
+        let before = countdown_in("JP", dob, date("2016-06-18"), &[]).unwrap();
+        let after = countdown_in("JP", dob, date("2016-06-19"), &[]).unwrap();
+        assert_eq!(before.eligible_from(), "2018-01-01");
+        assert_eq!(after.eligible_from(), "2016-01-01");
+        assert!(after.eligible());
+    }
+
+    #[test]
+    fn rejects_a_reference_before_birth() {
+        assert!(matches!(
+            countdown_in("US", date("2026-01-02"), date("2026-01-01"), &[]),
+            Err(EligibilityError::BornAfterReference { .. })
+        ));
+    }
+}
@@ -0,0 +1,725 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::convictions::{self, Conviction, DisenfranchisementPolicy, RightsStatus};
+use crate::dates;
+use crate::declarative::{Facts, NamedCondition};
+use crate::eligibility::{self, EligibilityStatus};
+use crate::error::EligibilityError;
+use crate::js;
+use crate::rules::{self, VotingAgeRule};
+
+#[wasm_bindgen(typescript_custom_section)]
+const ASSESSMENT_JSON: &'static str = r#"
+export interface Voter {
+  dateOfBirth?: string;
+  age?: number;
+  citizenships?: string[];
+  residentSince?: string;
+  registered?: boolean;
+  convictions?: Conviction[];
+}
+
+export interface CriterionResultJSON {
+  criterion: string;
+  outcome: "Pass" | "Fail" | "Unknown";
+  code: string;
+  reason: string;
+}
+
+export interface AssessmentJSON {
+  verdict: "Eligible" | "Ineligible" | "Indeterminate";
+  criteria: CriterionResultJSON[];
+  on: string;
+  jurisdiction: string;
+  ruleId: string;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "AssessmentJSON")]
+    pub type AssessmentJson;
+}
+
+/// What is known about a voter. Anything left out is unknown, not assumed.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Voter {
+    /// ISO-8601 date of birth; preferred over `age` when both are given.
+    pub date_of_birth: Option<String>,
+    pub age: Option<i32>,
+    /// ISO 3166-1 codes of every citizenship held.
+    pub citizenships: Option<Vec<String>>,
+    /// ISO-8601 date the voter moved to the jurisdiction.
+    pub resident_since: Option<String>,
+    pub registered: Option<bool>,
+    /// Every conviction; an empty list means a clean record.
+    pub convictions: Option<Vec<Conviction>>,
+}
+
+/// Result of a single criterion.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Outcome {
+    Pass,
+    Fail,
+    /// The data needed to decide was not given.
+    Unknown,
+}
+
+/// Overall result of all required criteria.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Verdict {
+    /// Every required criterion passed.
+    Eligible,
+    /// At least one required criterion failed.
+    Ineligible,
+    /// Nothing failed, but at least one required criterion could not be decided.
+    Indeterminate,
+}
+
+impl Verdict {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Eligible => "Eligible",
+            Verdict::Ineligible => "Ineligible",
+            Verdict::Indeterminate => "Indeterminate",
+        }
+    }
+}
+
+/// One criterion's outcome, with a stable `code` and an English `reason`.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CriterionResult {
+    criterion: String,
+    outcome: Outcome,
+    code: String,
+    reason: String,
+}
+
+impl CriterionResult {
+    pub fn new(criterion: &str, outcome: Outcome, code: &str, reason: String) -> Self {
+        CriterionResult {
+            criterion: criterion.to_string(),
+            outcome,
+            code: code.to_string(),
+            reason,
+        }
+    }
+
+    fn unknown(criterion: &str, reason: &str) -> Self {
+        CriterionResult::new(
+            criterion,
+            Outcome::Unknown,
+            "missing-data",
+            reason.to_string(),
+        )
+    }
+}
+
+#[wasm_bindgen]
+impl CriterionResult {
+    #[wasm_bindgen(getter)]
+    pub fn criterion(&self) -> String {
+        self.criterion.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+
+    /// Machine-readable reason, e.g. `below-voting-age` or `missing-data`.
+    #[wasm_bindgen(getter)]
+    pub fn code(&self) -> String {
+        self.code.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn reason(&self) -> String {
+        self.reason.clone()
+    }
+}
+
+/// What a criterion is judged against.
+pub struct Context<'a> {
+    pub rule: &'a VotingAgeRule,
+    pub on: NaiveDate,
+}
+
+/// A single eligibility criterion. Implement it to add checks to a [`Pipeline`].
+pub trait Criterion {
+    /// Name reported in results, such as `age`.
+    fn name(&self) -> &str;
+
+    fn evaluate(
+        &self,
+        voter: &Voter,
+        context: &Context,
+    ) -> Result<CriterionResult, EligibilityError>;
+}
+
+/// The voter has reached the voting age on the reference date.
+pub struct AgeCriterion;
+
+impl Criterion for AgeCriterion {
+    fn name(&self) -> &str {
+        "age"
+    }
+
+    fn evaluate(
+        &self,
+        voter: &Voter,
+        context: &Context,
+    ) -> Result<CriterionResult, EligibilityError> {
+        let result = match (&voter.date_of_birth, voter.age) {
+            (Some(dob), _) => {
+                eligibility::evaluate_dob(dates::parse_iso_date(dob)?, context.on, context.rule)?
+            }
+            (None, Some(age)) => eligibility::evaluate_age(age, context.rule)?,
+            (None, None) => {
+                return Ok(CriterionResult::unknown(
+                    self.name(),
+                    "No date of birth or age was given.",
+                ))
+            }
+        };
+        let reason = format!(
+            "Aged {}; the voting age is {}.",
+            result.age(),
+            result.threshold()
+        );
+        Ok(if result.status() != EligibilityStatus::NotYetEligible {
+            CriterionResult::new(self.name(), Outcome::Pass, "voting-age-reached", reason)
+        } else {
+            CriterionResult::new(self.name(), Outcome::Fail, "below-voting-age", reason)
+        })
+    }
+}
+
+/// The voter holds a citizenship that carries the vote in the jurisdiction.
+pub struct CitizenshipCriterion;
+
+impl Criterion for CitizenshipCriterion {
+    fn name(&self) -> &str {
+        "citizenship"
+    }
+
+    fn evaluate(
+        &self,
+        voter: &Voter,
+        context: &Context,
+    ) -> Result<CriterionResult, EligibilityError> {
+        let Some(citizenships) = &voter.citizenships else {
+            return Ok(CriterionResult::unknown(
+                self.name(),
+                "No citizenship was given.",
+            ));
+        };
+        let accepted = context.rule.accepted_citizenships();
+        Ok(
+            match citizenships
+                .iter()
+                .map(|code| code.trim().to_ascii_uppercase())
+                .find(|code| accepted.contains(code))
+            {
+                Some(code) => CriterionResult::new(
+                    self.name(),
+                    Outcome::Pass,
+                    "qualifying-citizenship",
+                    format!("Citizens of {} may vote here.", code),
+                ),
+                None => CriterionResult::new(
+                    self.name(),
+                    Outcome::Fail,
+                    "no-qualifying-citizenship",
+                    format!(
+                        "None of the citizenships given ({}) carries the vote here.",
+                        citizenships.join(", ")
+                    ),
+                ),
+            },
+        )
+    }
+}
+
+/// The voter has lived in the jurisdiction for its minimum residency period.
+pub struct ResidencyCriterion;
+
+impl Criterion for ResidencyCriterion {
+    fn name(&self) -> &str {
+        "residency"
+    }
+
+    fn evaluate(
+        &self,
+        voter: &Voter,
+        context: &Context,
+    ) -> Result<CriterionResult, EligibilityError> {
+        let required = context.rule.residency_days().unwrap_or_default();
+        let Some(since) = &voter.resident_since else {
+            return Ok(CriterionResult::unknown(
+                self.name(),
+                "No residency start date was given.",
+            ));
+        };
+        let days = (context.on - dates::parse_iso_date(since)?).num_days();
+        let reason = format!("Resident for {} days; {} are required.", days, required);
+        Ok(if days >= i64::from(required) {
+            CriterionResult::new(self.name(), Outcome::Pass, "residency-met", reason)
+        } else {
+            CriterionResult::new(self.name(), Outcome::Fail, "residency-too-short", reason)
+        })
+    }
+}
+
+/// The voter is on the electoral register.
+pub struct RegistrationCriterion;
+
+impl Criterion for RegistrationCriterion {
+    fn name(&self) -> &str {
+        "registration"
+    }
+
+    fn evaluate(
+        &self,
+        voter: &Voter,
+        _context: &Context,
+    ) -> Result<CriterionResult, EligibilityError> {
+        Ok(match voter.registered {
+            Some(true) => CriterionResult::new(
+                self.name(),
+                Outcome::Pass,
+                "registered",
+                "On the electoral register.".to_string(),
+            ),
+            Some(false) => CriterionResult::new(
+                self.name(),
+                Outcome::Fail,
+                "not-registered",
+                "Not on the electoral register.".to_string(),
+            ),
+            None => CriterionResult::unknown(self.name(), "Registration status was not given."),
+        })
+    }
+}
+
+/// The voter's criminal record does not bar them under the jurisdiction's conviction rules.
+pub struct ConvictionCriterion;
+
+impl Criterion for ConvictionCriterion {
+    fn name(&self) -> &str {
+        "criminal-record"
+    }
+
+    fn evaluate(
+        &self,
+        voter: &Voter,
+        context: &Context,
+    ) -> Result<CriterionResult, EligibilityError> {
+        let Some(convictions) = &voter.convictions else {
+            return Ok(CriterionResult::unknown(
+                self.name(),
+                "No criminal record was given.",
+            ));
+        };
+        let rights =
+            convictions::voting_rights(&context.rule.jurisdiction(), convictions, context.on)?;
+        let (outcome, code) = match rights.status() {
+            RightsStatus::Retained => (Outcome::Pass, "rights-retained"),
+            RightsStatus::Restored => (Outcome::Pass, "rights-restored"),
+            RightsStatus::Suspended => (Outcome::Fail, "rights-suspended"),
+            RightsStatus::Lost => (Outcome::Fail, "rights-lost"),
+        };
+        Ok(CriterionResult::new(
+            self.name(),
+            outcome,
+            code,
+            rights.reason(),
+        ))
+    }
+}
+
+/// A named condition from a loaded rule definition.
+pub struct ConditionCriterion(pub NamedCondition);
+
+impl Criterion for ConditionCriterion {
+    fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    fn evaluate(
+        &self,
+        voter: &Voter,
+        context: &Context,
+    ) -> Result<CriterionResult, EligibilityError> {
+        let facts = Facts {
+            voter,
+            on: Some(context.on),
+            leap_day: context.rule.leap_day(),
+        };
+        let condition = &self.0.require;
+        let outcome = condition.evaluate(&facts)?;
+        let (code, reason) = match outcome {
+            Outcome::Pass => ("condition-met", format!("Meets {}.", condition)),
+            Outcome::Fail => ("condition-not-met", format!("Does not meet {}.", condition)),
+            Outcome::Unknown => (
+                "missing-data",
+                format!("Not enough is known to decide {}.", condition),
+            ),
+        };
+        Ok(CriterionResult::new(self.name(), outcome, code, reason))
+    }
+}
+
+/// Required criteria, evaluated in order into a single verdict.
+#[derive(Default)]
+pub struct Pipeline {
+    criteria: Vec<Box<dyn Criterion>>,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Pipeline::default()
+    }
+
+    /// Adds a required criterion.
+    pub fn with(mut self, criterion: impl Criterion + 'static) -> Self {
+        self.criteria.push(Box::new(criterion));
+        self
+    }
+
+    /// The criteria `rule`'s jurisdiction requires: age always, citizenship wherever the
+    /// jurisdiction is known, residency and registration where the rule asks for them, the
+    /// criminal record where a conviction can cost the vote, and any conditions of a loaded
+    /// rule definition.
+    pub fn for_rule(rule: &VotingAgeRule) -> Self {
+        let mut pipeline = Pipeline::new().with(AgeCriterion);
+        if !rule.accepted_citizenships().is_empty() {
+            pipeline = pipeline.with(CitizenshipCriterion);
+        }
+        if rule.residency_days().is_some() {
+            pipeline = pipeline.with(ResidencyCriterion);
+        }
+        if rule.registration_required() {
+            pipeline = pipeline.with(RegistrationCriterion);
+        }
+        let policy = convictions::policy_for(&rule.jurisdiction()).map(|spec| spec.policy);
+        if policy.is_some_and(|policy| policy != DisenfranchisementPolicy::NeverLost) {
+            pipeline = pipeline.with(ConvictionCriterion);
+        }
+        for condition in rule.conditions() {
+            pipeline = pipeline.with(ConditionCriterion(condition.clone()));
+        }
+        pipeline
+    }
+
+    /// Names of the criteria, in evaluation order.
+    pub fn names(&self) -> Vec<&str> {
+        self.criteria
+            .iter()
+            .map(|criterion| criterion.name())
+            .collect()
+    }
+
+    pub fn evaluate(
+        &self,
+        voter: &Voter,
+        rule: &VotingAgeRule,
+        on: NaiveDate,
+    ) -> Result<Assessment, EligibilityError> {
+        let context = Context { rule, on };
+        let criteria = self
+            .criteria
+            .iter()
+            .map(|criterion| criterion.evaluate(voter, &context))
+            .collect::<Result<Vec<_>, _>>()?;
+        let outcomes = || criteria.iter().map(|result| result.outcome);
+        let verdict = if outcomes().any(|outcome| outcome == Outcome::Fail) {
+            Verdict::Ineligible
+        } else if outcomes().any(|outcome| outcome == Outcome::Unknown) {
+            Verdict::Indeterminate
+        } else {
+            Verdict::Eligible
+        };
+        Ok(Assessment {
+            verdict,
+            criteria,
+            on,
+            jurisdiction: rule.jurisdiction(),
+            rule_id: rule.id(),
+        })
+    }
+}
+
+/// The verdict on a voter and the outcome of every criterion behind it.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Assessment {
+    verdict: Verdict,
+    criteria: Vec<CriterionResult>,
+    #[serde(serialize_with = "dates::serialize_iso")]
+    on: NaiveDate,
+    jurisdiction: String,
+    rule_id: String,
+}
+
+impl Assessment {
+    pub fn results(&self) -> &[CriterionResult] {
+        &self.criteria
+    }
+
+    /// The result of the criterion called `name`, if it was required.
+    pub fn result(&self, name: &str) -> Option<&CriterionResult> {
+        self.criteria.iter().find(|result| result.criterion == name)
+    }
+}
+
+#[wasm_bindgen]
+impl Assessment {
+    #[wasm_bindgen(getter)]
+    pub fn verdict(&self) -> Verdict {
+        self.verdict
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn eligible(&self) -> bool {
+        self.verdict == Verdict::Eligible
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn criteria(&self) -> Vec<CriterionResult> {
+        self.criteria.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn jurisdiction(&self) -> String {
+        self.jurisdiction.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = ruleId)]
+    pub fn rule_id(&self) -> String {
+        self.rule_id.clone()
+    }
+
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json(&self) -> Result<AssessmentJson, JsError> {
+        Ok(js::to_js(self)?.unchecked_into())
+    }
+}
+
+/// Assesses `voter` against every criterion `jurisdiction` requires, as of `on`.
+pub fn assess(
+    jurisdiction: &str,
+    voter: &Voter,
+    on: NaiveDate,
+) -> Result<Assessment, EligibilityError> {
+    let rule = rules::lookup_as_of(jurisdiction, on)?;
+    Pipeline::for_rule(&rule).evaluate(voter, &rule, on)
+}
+
+/// Checks age, citizenship, residency and registration as `jurisdiction` requires.
+///
+/// Missing facts make the verdict `Indeterminate` rather than `Ineligible`. `on` may be an
+/// ISO-8601 string or a JS `Date`.
+#[wasm_bindgen]
+pub fn assess_voter(
+    jurisdiction: &str,
+    voter: JsValue,
+    on: JsValue,
+) -> Result<Assessment, JsError> {
+    let voter: Voter = js::options_from_js(voter)?;
+    Ok(assess(jurisdiction, &voter, dates::date_from_js(&on)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::declarative::{self, RuleFormat};
+
+    fn date(text: &str) -> NaiveDate {
+        dates::parse_iso_date(text).unwrap()
+    }
+
+    fn voter(json: &str) -> Voter {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn outcomes(assessment: &Assessment) -> Vec<(String, Outcome)> {
+        assessment
+            .results()
+            .iter()
+            .map(|result| (result.criterion(), result.outcome()))
+            .collect()
+    }
+
+    #[test]
+    fn requires_what_the_jurisdiction_asks_for() {
+        let names = |code: &str| {
+            Pipeline::for_rule(&rules::lookup(code).unwrap())
+                .names()
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names("US-CA"),
+            [
+                "age",
+                "citizenship",
+                "residency",
+                "registration",
+                "criminal-record"
+            ]
+        );
+        assert_eq!(names("DE"), ["age", "citizenship"]);
+    }
+
+    #[test]
+    fn every_criterion_passing_is_eligible() {
+        let assessment = assess(
+            "US-CA",
+            &voter(
+                r#"{"dateOfBirth": "2000-01-01", "citizenships": ["mx", "us"],
+                    "residentSince": "2026-10-01", "registered": true, "convictions": []}"#,
+            ),
+            date("2026-11-03"),
+        )
+        .unwrap();
+        assert_eq!(assessment.verdict(), Verdict::Eligible);
+        assert_eq!(
+            assessment.result("citizenship").unwrap().reason(),
+            "Citizens of US may vote here."
+        );
+        assert_eq!(
+            assessment.result("residency").unwrap().code(),
+            "residency-met"
+        );
+    }
+
+    #[test]
+    fn a_failure_outweighs_missing_data() {
+        let on = date("2026-11-03");
+        let partial = assess("US-CA", &voter(r#"{"age": 30}"#), on).unwrap();
+        assert_eq!(partial.verdict(), Verdict::Indeterminate);
+        assert_eq!(
+            outcomes(&partial),
+            [
+                ("age".to_string(), Outcome::Pass),
+                ("citizenship".to_string(), Outcome::Unknown),
+                ("residency".to_string(), Outcome::Unknown),
+                ("registration".to_string(), Outcome::Unknown),
+                ("criminal-record".to_string(), Outcome::Unknown),
+            ]
+        );
+
+        let jailed = voter(
+            r#"{"age": 30, "convictions": [{"status": "incarcerated", "releaseDate": "2027-01-01"}]}"#,
+        );
+        let assessment = assess("US-CA", &jailed, on).unwrap();
+        assert_eq!(assessment.verdict(), Verdict::Ineligible);
+        assert_eq!(
+            assessment.result("criminal-record").unwrap().code(),
+            "rights-suspended"
+        );
+    }
+
+    #[test]
+    fn checks_residency_and_age_on_the_reference_date() {
+        let assessment = assess(
+            "US-CA",
+            &voter(r#"{"dateOfBirth": "2008-11-04", "residentSince": "2026-10-20"}"#),
+            date("2026-11-03"),
+        )
+        .unwrap();
+        assert_eq!(
+            assessment.result("age").unwrap().reason(),
+            "Aged 17; the voting age is 18."
+        );
+        assert_eq!(
+            assessment.result("residency").unwrap().reason(),
+            "Resident for 14 days; 15 are required."
+        );
+        assert_eq!(assessment.verdict(), Verdict::Ineligible);
+    }
+
+    #[test]
+    fn evaluates_conditions_of_loaded_rules() {
+        declarative::load(
+            r#"{"rules": [{"jurisdiction": "XH", "votingAge": 16, "registrationRequired": false,
+                "conditions": [{"name": "local", "require": {"compare": {
+                    "left": {"fact": "residentSince", "offset": {"years": 1}}, "op": "<=", "right": {"fact": "on"}}}}]}]}"#,
+            RuleFormat::Json,
+        )
+        .unwrap();
+        let on = date("2026-11-03");
+        let newcomer =
+            voter(r#"{"age": 40, "citizenships": ["XH"], "residentSince": "2026-01-01"}"#);
+        let assessment = assess("XH", &newcomer, on).unwrap();
+        let local = assessment.result("local").unwrap();
+        assert_eq!(local.code(), "condition-not-met");
+        assert_eq!(local.reason(), "Does not meet residentSince +1y <= on.");
+
+        let unknown = assess("XH", &voter(r#"{"age": 40, "citizenships": ["XH"]}"#), on).unwrap();
+        assert_eq!(unknown.verdict(), Verdict::Indeterminate);
+
+        let garbled = voter(r#"{"age": 40, "residentSince": "soon"}"#);
+        assert!(matches!(
+            assess("XH", &garbled, on),
+            Err(EligibilityError::InvalidDate(_))
+        ));
+    }
+
+    #[test]
+    fn an_unmet_condition_does_not_fail_the_age_criterion() {
+        declarative::load(
+            r#"{"rules": [{"jurisdiction": "XI", "votingAge": 18, "registrationRequired": false,
+                "conditions": [{"name": "adult", "require": {"compare": {
+                    "left": {"fact": "age"}, "op": ">=", "right": 21}}}]}]}"#,
+            RuleFormat::Json,
+        )
+        .unwrap();
+        let assessment = assess("XI", &voter(r#"{"age": 19}"#), date("2026-11-03")).unwrap();
+        assert_eq!(
+            outcomes(&assessment)[..],
+            [
+                ("age".to_string(), Outcome::Pass),
+                ("citizenship".to_string(), Outcome::Unknown),
+                ("adult".to_string(), Outcome::Fail),
+            ]
+        );
+        assert_eq!(assessment.verdict(), Verdict::Ineligible);
+    }
+
+    #[test]
+    fn runs_custom_criteria() {
+        struct AlwaysUnknown;
+
+        impl Criterion for AlwaysUnknown {
+            fn name(&self) -> &str {
+                "oracle"
+            }
+
+            fn evaluate(
+                &self,
+                _voter: &Voter,
+                _context: &Context,
+            ) -> Result<CriterionResult, EligibilityError> {
+                Ok(CriterionResult::unknown(self.name(), "No one knows."))
+            }
+        }
+
+        let rule = VotingAgeRule::default_rule();
+        let pipeline = Pipeline::new().with(AgeCriterion).with(AlwaysUnknown);
+        let assessment = pipeline
+            .evaluate(&voter(r#"{"age": 18}"#), &rule, date("2026-11-03"))
+            .unwrap();
+        assert_eq!(assessment.verdict(), Verdict::Indeterminate);
+        assert_eq!(assessment.result("oracle").unwrap().code(), "missing-data");
+    }
+}
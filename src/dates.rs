@@ -0,0 +1,237 @@
+use chrono::{DateTime, Datelike, Months, NaiveDate, Utc};
+use serde::Serializer;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::error::EligibilityError;
+
+/// How a Feb 29 birthday is observed in years without a Feb 29.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeapDayPolicy {
+    /// The birthday is observed on Feb 28.
+    Feb28,
+    /// The birthday is observed on Mar 1.
+    Mar1,
+}
+
+/// Parses an ISO-8601 date (`2006-05-14`) or date-time (`2006-05-14T09:30:00Z`), keeping the date.
+pub fn parse_iso_date(text: &str) -> Result<NaiveDate, EligibilityError> {
+    let text = text.trim();
+    let date_part = text.split(['T', ' ']).next().unwrap_or_default();
+    NaiveDate::parse_from_str(date_part, "%Y-%m-%d")
+        .map_err(|_| EligibilityError::InvalidDate(text.to_string()))
+}
+
+/// Reads a date from a JS value holding either an ISO-8601 string or a `Date`.
+///
+/// `Date` values are read in UTC, which is how JS parses bare `YYYY-MM-DD` strings.
+pub fn date_from_js(value: &JsValue) -> Result<NaiveDate, EligibilityError> {
+    if let Some(text) = value.as_string() {
+        return parse_iso_date(&text);
+    }
+    let date = value
+        .dyn_ref::<js_sys::Date>()
+        .filter(|date| !date.get_time().is_nan())
+        .ok_or_else(|| EligibilityError::InvalidDate(format!("{:?}", value)))?;
+    NaiveDate::from_ymd_opt(
+        date.get_utc_full_year() as i32,
+        date.get_utc_month() + 1,
+        date.get_utc_date(),
+    )
+    .ok_or_else(|| EligibilityError::InvalidDate(format!("{:?}", value)))
+}
+
+/// The date on which someone born on `dob` turns `years` old.
+pub fn anniversary(
+    dob: NaiveDate,
+    years: u32,
+    policy: LeapDayPolicy,
+) -> Result<NaiveDate, EligibilityError> {
+    let out_of_range = || EligibilityError::DateOutOfRange { date: dob, years };
+    let year = i32::try_from(years)
+        .ok()
+        .and_then(|years| dob.year().checked_add(years))
+        .ok_or_else(out_of_range)?;
+    dob.with_year(year)
+        .or_else(|| match policy {
+            LeapDayPolicy::Feb28 => NaiveDate::from_ymd_opt(year, 2, 28),
+            LeapDayPolicy::Mar1 => NaiveDate::from_ymd_opt(year, 3, 1),
+        })
+        .ok_or_else(out_of_range)
+}
+
+/// Completed years of age on `on` for someone born on `dob`.
+pub fn age_on(
+    dob: NaiveDate,
+    on: NaiveDate,
+    policy: LeapDayPolicy,
+) -> Result<i32, EligibilityError> {
+    if on < dob {
+        return Err(EligibilityError::BornAfterReference { dob, on });
+    }
+    let mut years = (on.year() - dob.year()) as u32;
+    if anniversary(dob, years, policy)? > on {
+        years -= 1;
+    }
+    Ok(years as i32)
+}
+
+/// Reads an instant from a JS value holding epoch milliseconds, a `Date`, or an RFC 3339 string.
+pub fn instant_from_js(value: &JsValue) -> Result<DateTime<Utc>, EligibilityError> {
+    let invalid = || EligibilityError::InvalidTimestamp(format!("{:?}", value));
+    if let Some(text) = value.as_string() {
+        return DateTime::parse_from_rfc3339(text.trim())
+            .map(|instant| instant.with_timezone(&Utc))
+            .map_err(|_| EligibilityError::InvalidTimestamp(text));
+    }
+    let millis = match value.dyn_ref::<js_sys::Date>() {
+        Some(date) => date.get_time(),
+        None => value.as_f64().ok_or_else(invalid)?,
+    };
+    if !millis.is_finite() {
+        return Err(invalid());
+    }
+    DateTime::from_timestamp_millis(millis as i64).ok_or_else(invalid)
+}
+
+/// Today's date in UTC, read from the JS clock under wasm and the system clock elsewhere.
+pub fn today() -> NaiveDate {
+    #[cfg(target_arch = "wasm32")]
+    let millis = js_sys::Date::now() as i64;
+    #[cfg(not(target_arch = "wasm32"))]
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as i64);
+    DateTime::from_timestamp_millis(millis)
+        .expect("the clock reads a representable instant")
+        .date_naive()
+}
+
+/// Whole years, months and days from `from` to `to` (which must not be earlier), counted
+/// the way a calendar is read: 2024-01-31 to 2024-03-01 is 1 month and 1 day.
+pub fn calendar_difference(from: NaiveDate, to: NaiveDate) -> (u32, u32, u32) {
+    let mut months =
+        ((to.year() - from.year()) * 12 + to.month() as i32 - from.month() as i32) as u32;
+    let mut anchor = from + Months::new(months);
+    if anchor > to {
+        months -= 1;
+        anchor = from + Months::new(months);
+    }
+    let days = (to - anchor).num_days() as u32;
+    (months / 12, months % 12, days)
+}
+
+/// Serializes a date as an ISO-8601 string.
+pub fn serialize_iso<S: Serializer>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(date)
+}
+
+/// Serializes an optional date as an ISO-8601 string or `null`.
+pub fn serialize_iso_opt<S: Serializer>(
+    date: &Option<NaiveDate>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match date {
+        Some(date) => serializer.collect_str(date),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(text: &str) -> NaiveDate {
+        parse_iso_date(text).unwrap()
+    }
+
+    #[test]
+    fn leap_day_anniversaries_follow_the_policy() {
+        let dob = date("2008-02-29");
+        assert_eq!(
+            anniversary(dob, 18, LeapDayPolicy::Feb28),
+            Ok(date("2026-02-28"))
+        );
+        assert_eq!(
+            anniversary(dob, 18, LeapDayPolicy::Mar1),
+            Ok(date("2026-03-01"))
+        );
+        assert_eq!(
+            anniversary(dob, 16, LeapDayPolicy::Mar1),
+            Ok(date("2024-02-29"))
+        );
+    }
+
+    #[test]
+    fn anniversaries_past_the_calendar_are_out_of_range() {
+        let dob = NaiveDate::MAX.with_day(1).unwrap();
+        assert_eq!(
+            anniversary(dob, 18, LeapDayPolicy::Mar1),
+            Err(EligibilityError::DateOutOfRange {
+                date: dob,
+                years: 18
+            })
+        );
+        assert_eq!(
+            anniversary(date("2008-05-14"), u32::MAX, LeapDayPolicy::Mar1),
+            Err(EligibilityError::DateOutOfRange {
+                date: date("2008-05-14"),
+                years: u32::MAX
+            })
+        );
+    }
+
+    #[test]
+    fn leap_day_birthdays_age_on_the_observed_day() {
+        let dob = date("2008-02-29");
+        assert_eq!(
+            age_on(dob, date("2026-02-28"), LeapDayPolicy::Feb28),
+            Ok(18)
+        );
+        assert_eq!(age_on(dob, date("2026-02-28"), LeapDayPolicy::Mar1), Ok(17));
+        assert_eq!(age_on(dob, date("2026-03-01"), LeapDayPolicy::Mar1), Ok(18));
+        assert_eq!(
+            age_on(dob, date("2024-02-29"), LeapDayPolicy::Feb28),
+            Ok(16)
+        );
+        assert_eq!(
+            age_on(dob, date("2024-02-28"), LeapDayPolicy::Feb28),
+            Ok(15)
+        );
+    }
+
+    #[test]
+    fn age_on_rejects_a_reference_before_birth() {
+        let dob = date("2008-05-14");
+        assert_eq!(age_on(dob, dob, LeapDayPolicy::Feb28), Ok(0));
+        assert_eq!(
+            age_on(dob, date("2008-05-13"), LeapDayPolicy::Feb28),
+            Err(EligibilityError::BornAfterReference {
+                dob,
+                on: date("2008-05-13")
+            })
+        );
+    }
+
+    #[test]
+    fn parses_dates_and_date_times() {
+        assert_eq!(date(" 2006-05-14T09:30:00Z "), date("2006-05-14"));
+        assert_eq!(
+            parse_iso_date("14/05/2006"),
+            Err(EligibilityError::InvalidDate("14/05/2006".to_string()))
+        );
+    }
+
+    #[test]
+    fn calendar_difference_reads_like_a_calendar() {
+        assert_eq!(
+            calendar_difference(date("2024-01-31"), date("2024-03-01")),
+            (0, 1, 1)
+        );
+        assert_eq!(
+            calendar_difference(date("2020-05-14"), date("2026-05-13")),
+            (5, 11, 29)
+        );
+    }
+}
@@ -0,0 +1,1046 @@
+use std::fmt;
+
+use chrono::{Days, Months, NaiveDate};
+use serde::de::{self, Deserializer, MapAccess, Visitor};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::criteria::{Outcome, Voter};
+use crate::dates::{self, LeapDayPolicy};
+use crate::error::EligibilityError;
+use crate::js;
+use crate::rules::{self, VotingAgeRule};
+use crate::validation::MAX_AGE;
+
+/// Key under which the `toml` crate hands a native TOML date to a deserializer.
+const TOML_DATETIME_KEY: &str = "$__toml_private_datetime";
+
+#[wasm_bindgen(typescript_custom_section)]
+const RULE_DEFINITION_TS: &'static str = r#"
+export type Operand = number | string | { fact: Fact; offset?: { years?: number; months?: number; days?: number } };
+export type Fact = "age" | "dateOfBirth" | "on" | "residentSince";
+
+export type Condition =
+  | { all: Condition[] }
+  | { any: Condition[] }
+  | { not: Condition }
+  | { compare: { left: Operand; op: "<" | "<=" | "==" | "!=" | ">=" | ">"; right: Operand } }
+  | { citizenOf: string[] }
+  | { registered: boolean };
+
+export interface RuleDefinition {
+  id?: string;
+  jurisdiction: string;
+  effectiveFrom?: string;
+  votingAge: number;
+  leapDay?: "feb28" | "mar1";
+  preregistrationAge?: number;
+  registrationLeadMonths?: number;
+  primaryByGeneral?: boolean;
+  residencyDays?: number;
+  registrationRequired?: boolean;
+  otherCitizenships?: string[];
+  conditions?: { name: string; require: Condition }[];
+}
+
+export interface DefinitionError {
+  message: string;
+  path: string;
+  line: number | null;
+  column: number | null;
+}
+"#;
+
+/// Text format of a rule definition file.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleFormat {
+    Json,
+    Toml,
+}
+
+/// Why a rule definition file was rejected, with the path of the offending value
+/// (`rules[0].conditions[1].require.compare.op`) and, where the parser knows it, a 1-based
+/// line and column.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DefinitionError {
+    pub message: String,
+    pub path: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+impl DefinitionError {
+    fn at(path: String, message: String) -> Self {
+        DefinitionError {
+            message,
+            path,
+            line: None,
+            column: None,
+        }
+    }
+
+    fn located(mut self, source: &str, offset: usize) -> Self {
+        let before = &source[..offset.min(source.len())];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        self.line = Some(before.matches('\n').count() + 1);
+        self.column = Some(before[line_start..].chars().count() + 1);
+        self
+    }
+}
+
+impl fmt::Display for DefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(
+                f,
+                "{} (at `{}`, line {}, column {})",
+                self.message, self.path, line, column
+            ),
+            _ => write!(f, "{} (at `{}`)", self.message, self.path),
+        }
+    }
+}
+
+/// A fact about the voter or the election that a condition can test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Fact {
+    /// Completed years on the evaluation date.
+    Age,
+    DateOfBirth,
+    /// The election or reference date.
+    On,
+    ResidentSince,
+}
+
+impl Fact {
+    fn is_date(self) -> bool {
+        self != Fact::Age
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Fact::Age => "age",
+            Fact::DateOfBirth => "dateOfBirth",
+            Fact::On => "on",
+            Fact::ResidentSince => "residentSince",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        [Fact::Age, Fact::DateOfBirth, Fact::On, Fact::ResidentSince]
+            .into_iter()
+            .find(|fact| fact.as_str() == name)
+    }
+}
+
+/// Calendar offset added to a date fact, e.g. 18 years after `dateOfBirth`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Offset {
+    pub years: i32,
+    pub months: i32,
+    pub days: i32,
+}
+
+impl Offset {
+    fn is_zero(self) -> bool {
+        self == Offset::default()
+    }
+
+    /// Years and months together, or `None` if they do not fit in an `i32`.
+    fn total_months(self) -> Option<i32> {
+        self.years.checked_mul(12)?.checked_add(self.months)
+    }
+
+    /// `date` shifted by this offset, or `None` outside the supported calendar.
+    fn apply(self, date: NaiveDate) -> Option<NaiveDate> {
+        let months = self.total_months()?;
+        let date = match months.unsigned_abs() {
+            0 => date,
+            n if months > 0 => date.checked_add_months(Months::new(n))?,
+            n => date.checked_sub_months(Months::new(n))?,
+        };
+        match self.days.unsigned_abs() {
+            0 => Some(date),
+            n if self.days > 0 => date.checked_add_days(Days::new(n.into())),
+            n => date.checked_sub_days(Days::new(n.into())),
+        }
+    }
+}
+
+/// One side of a comparison: a number, a date, or a fact shifted by an offset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operand {
+    Number(i64),
+    Date(NaiveDate),
+    Fact { fact: Fact, offset: Offset },
+}
+
+impl Operand {
+    fn is_date(&self) -> bool {
+        match self {
+            Operand::Number(_) => false,
+            Operand::Date(_) => true,
+            Operand::Fact { fact, .. } => fact.is_date(),
+        }
+    }
+
+    fn value(&self, facts: &Facts) -> Result<Option<Value>, EligibilityError> {
+        Ok(match self {
+            Operand::Number(n) => Some(Value::Number(*n)),
+            Operand::Date(date) => Some(Value::Date(*date)),
+            Operand::Fact { fact, offset } => match fact {
+                Fact::Age => facts.age()?.map(|age| Value::Number(age.into())),
+                _ => facts
+                    .date(*fact)?
+                    .and_then(|date| offset.apply(date))
+                    .map(Value::Date),
+            },
+        })
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Number(n) => write!(f, "{}", n),
+            Operand::Date(date) => write!(f, "{}", date),
+            Operand::Fact { fact, offset } => {
+                f.write_str(fact.as_str())?;
+                for (amount, unit) in [
+                    (offset.years, "y"),
+                    (offset.months, "m"),
+                    (offset.days, "d"),
+                ] {
+                    if amount != 0 {
+                        write!(f, " {:+}{}", amount, unit)?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Operand {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(OperandVisitor)
+    }
+}
+
+struct OperandVisitor;
+
+impl<'de> Visitor<'de> for OperandVisitor {
+    type Value = Operand;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a number, a fact name, a YYYY-MM-DD date or { fact, offset }")
+    }
+
+    fn visit_i64<E: de::Error>(self, n: i64) -> Result<Operand, E> {
+        Ok(Operand::Number(n))
+    }
+
+    fn visit_u64<E: de::Error>(self, n: u64) -> Result<Operand, E> {
+        i64::try_from(n)
+            .map(Operand::Number)
+            .map_err(|_| E::custom(format!("{} is too large", n)))
+    }
+
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<Operand, E> {
+        if let Some(fact) = Fact::parse(text) {
+            return Ok(Operand::Fact {
+                fact,
+                offset: Offset::default(),
+            });
+        }
+        dates::parse_iso_date(text).map(Operand::Date).map_err(|_| {
+            E::custom(format!(
+                "`{}` is neither a fact (age, dateOfBirth, on, residentSince) nor a YYYY-MM-DD date",
+                text
+            ))
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Operand, A::Error> {
+        let mut fact = None;
+        let mut offset = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "fact" => fact = Some(map.next_value::<Fact>()?),
+                "offset" => offset = Some(map.next_value::<Offset>()?),
+                TOML_DATETIME_KEY => {
+                    return DateVisitor
+                        .visit_str(&map.next_value::<String>()?)
+                        .map(Operand::Date)
+                }
+                _ => return Err(de::Error::unknown_field(&key, &["fact", "offset"])),
+            }
+        }
+        let fact = fact.ok_or_else(|| de::Error::missing_field("fact"))?;
+        let offset = offset.unwrap_or_default();
+        if !fact.is_date() && !offset.is_zero() {
+            return Err(de::Error::custom("only date facts can take an offset"));
+        }
+        Ok(Operand::Fact { fact, offset })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Comparator {
+    #[serde(rename = "<")]
+    Less,
+    #[serde(rename = "<=")]
+    LessOrEqual,
+    #[serde(rename = "==")]
+    Equal,
+    #[serde(rename = "!=")]
+    NotEqual,
+    #[serde(rename = ">=")]
+    GreaterOrEqual,
+    #[serde(rename = ">")]
+    Greater,
+}
+
+impl Comparator {
+    fn as_str(self) -> &'static str {
+        match self {
+            Comparator::Less => "<",
+            Comparator::LessOrEqual => "<=",
+            Comparator::Equal => "==",
+            Comparator::NotEqual => "!=",
+            Comparator::GreaterOrEqual => ">=",
+            Comparator::Greater => ">",
+        }
+    }
+
+    fn holds<T: Ord>(self, left: T, right: T) -> bool {
+        match self {
+            Comparator::Less => left < right,
+            Comparator::LessOrEqual => left <= right,
+            Comparator::Equal => left == right,
+            Comparator::NotEqual => left != right,
+            Comparator::GreaterOrEqual => left >= right,
+            Comparator::Greater => left > right,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawComparison {
+    left: Operand,
+    op: Comparator,
+    right: Operand,
+}
+
+/// `left op right`, with both sides numbers or both sides dates.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "RawComparison")]
+pub struct Comparison {
+    pub left: Operand,
+    pub op: Comparator,
+    pub right: Operand,
+}
+
+impl TryFrom<RawComparison> for Comparison {
+    type Error = String;
+
+    fn try_from(raw: RawComparison) -> Result<Self, String> {
+        if raw.left.is_date() != raw.right.is_date() {
+            return Err(format!(
+                "cannot compare `{}` with `{}`: one is a date and the other a number",
+                raw.left, raw.right
+            ));
+        }
+        Ok(Comparison {
+            left: raw.left,
+            op: raw.op,
+            right: raw.right,
+        })
+    }
+}
+
+/// A test on a voter, combined with `all`, `any` and `not`.
+///
+/// Conditions are three-valued: a fact that was not given makes a test `Unknown` rather
+/// than false, and `all`/`any`/`not` carry that through.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub enum Condition {
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+    Not(Box<Condition>),
+    Compare(Comparison),
+    /// Holds at least one of these ISO 3166-1 citizenships.
+    CitizenOf(Vec<String>),
+    /// Is, or is not, on the electoral register.
+    Registered(bool),
+}
+
+impl Condition {
+    /// Rejects an empty `all` or `any`, here or nested under `path`: one would always pass
+    /// and the other always fail, which is never what the author meant.
+    fn validate(&self, path: String) -> Result<(), DefinitionError> {
+        let (name, conditions) = match self {
+            Condition::All(conditions) => ("all", conditions),
+            Condition::Any(conditions) => ("any", conditions),
+            Condition::Not(condition) => return condition.validate(format!("{}.not", path)),
+            Condition::Compare(comparison) => {
+                let sides = [("left", &comparison.left), ("right", &comparison.right)];
+                for (side, operand) in sides {
+                    if let Operand::Fact { offset, .. } = operand {
+                        if offset.total_months().is_none() {
+                            return Err(DefinitionError::at(
+                                format!("{}.compare.{}.offset", path, side),
+                                "offset is outside the supported calendar".to_string(),
+                            ));
+                        }
+                    }
+                }
+                return Ok(());
+            }
+            _ => return Ok(()),
+        };
+        let path = format!("{}.{}", path, name);
+        if conditions.is_empty() {
+            return Err(DefinitionError::at(
+                path,
+                format!("`{}` needs at least one condition", name),
+            ));
+        }
+        for (index, condition) in conditions.iter().enumerate() {
+            condition.validate(format!("{}[{}]", path, index))?;
+        }
+        Ok(())
+    }
+
+    pub fn evaluate(&self, facts: &Facts) -> Result<Outcome, EligibilityError> {
+        Ok(match self {
+            Condition::All(conditions) => {
+                let mut outcome = Outcome::Pass;
+                for condition in conditions {
+                    match condition.evaluate(facts)? {
+                        Outcome::Fail => return Ok(Outcome::Fail),
+                        Outcome::Unknown => outcome = Outcome::Unknown,
+                        Outcome::Pass => {}
+                    }
+                }
+                outcome
+            }
+            Condition::Any(conditions) => {
+                let mut outcome = Outcome::Fail;
+                for condition in conditions {
+                    match condition.evaluate(facts)? {
+                        Outcome::Pass => return Ok(Outcome::Pass),
+                        Outcome::Unknown => outcome = Outcome::Unknown,
+                        Outcome::Fail => {}
+                    }
+                }
+                outcome
+            }
+            Condition::Not(condition) => match condition.evaluate(facts)? {
+                Outcome::Pass => Outcome::Fail,
+                Outcome::Fail => Outcome::Pass,
+                Outcome::Unknown => Outcome::Unknown,
+            },
+            Condition::Compare(comparison) => {
+                let left = comparison.left.value(facts)?;
+                let right = comparison.right.value(facts)?;
+                match (left, right) {
+                    (Some(Value::Number(left)), Some(Value::Number(right))) => {
+                        outcome(comparison.op.holds(left, right))
+                    }
+                    (Some(Value::Date(left)), Some(Value::Date(right))) => {
+                        outcome(comparison.op.holds(left, right))
+                    }
+                    _ => Outcome::Unknown,
+                }
+            }
+            Condition::CitizenOf(countries) => match &facts.voter.citizenships {
+                Some(held) => outcome(held.iter().any(|code| {
+                    countries
+                        .iter()
+                        .any(|country| country.eq_ignore_ascii_case(code.trim()))
+                })),
+                None => Outcome::Unknown,
+            },
+            Condition::Registered(expected) => match facts.voter.registered {
+                Some(registered) => outcome(registered == *expected),
+                None => Outcome::Unknown,
+            },
+        })
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |f: &mut fmt::Formatter<'_>, name: &str, conditions: &[Condition]| {
+            write!(f, "{}(", name)?;
+            for (index, condition) in conditions.iter().enumerate() {
+                if index > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{}", condition)?;
+            }
+            f.write_str(")")
+        };
+        match self {
+            Condition::All(conditions) => list(f, "all", conditions),
+            Condition::Any(conditions) => list(f, "any", conditions),
+            Condition::Not(condition) => write!(f, "not({})", condition),
+            Condition::Compare(comparison) => write!(
+                f,
+                "{} {} {}",
+                comparison.left,
+                comparison.op.as_str(),
+                comparison.right
+            ),
+            Condition::CitizenOf(countries) => write!(f, "citizenOf({})", countries.join(", ")),
+            Condition::Registered(registered) => write!(f, "registered == {}", registered),
+        }
+    }
+}
+
+fn outcome(holds: bool) -> Outcome {
+    if holds {
+        Outcome::Pass
+    } else {
+        Outcome::Fail
+    }
+}
+
+enum Value {
+    Number(i64),
+    Date(NaiveDate),
+}
+
+/// What conditions are evaluated against: a voter, the evaluation date if there is one, and
+/// the leap-day policy used to work out ages.
+pub struct Facts<'a> {
+    pub voter: &'a Voter,
+    pub on: Option<NaiveDate>,
+    pub leap_day: LeapDayPolicy,
+}
+
+impl Facts<'_> {
+    fn age(&self) -> Result<Option<i32>, EligibilityError> {
+        match (&self.voter.date_of_birth, self.on) {
+            (Some(dob), Some(on)) => Ok(Some(dates::age_on(
+                dates::parse_iso_date(dob)?,
+                on,
+                self.leap_day,
+            )?)),
+            _ => Ok(self.voter.age),
+        }
+    }
+
+    fn date(&self, fact: Fact) -> Result<Option<NaiveDate>, EligibilityError> {
+        let text = match fact {
+            Fact::On => return Ok(self.on),
+            Fact::DateOfBirth => &self.voter.date_of_birth,
+            Fact::ResidentSince => &self.voter.resident_since,
+            Fact::Age => return Ok(None),
+        };
+        text.as_deref().map(dates::parse_iso_date).transpose()
+    }
+}
+
+/// A named condition a rule adds to the built-in criteria.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NamedCondition {
+    pub name: String,
+    pub require: Condition,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum LeapDay {
+    Feb28,
+    #[default]
+    Mar1,
+}
+
+/// One rule as written in a definition file.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RuleDefinition {
+    pub id: Option<String>,
+    #[serde(deserialize_with = "jurisdiction_code")]
+    pub jurisdiction: String,
+    #[serde(default, deserialize_with = "optional_date")]
+    pub effective_from: Option<NaiveDate>,
+    pub voting_age: u8,
+    #[serde(default)]
+    leap_day: LeapDay,
+    pub preregistration_age: Option<u8>,
+    #[serde(default)]
+    pub registration_lead_months: u8,
+    #[serde(default)]
+    pub primary_by_general: bool,
+    pub residency_days: Option<u16>,
+    #[serde(default = "required")]
+    pub registration_required: bool,
+    #[serde(default, deserialize_with = "country_codes")]
+    pub other_citizenships: Vec<String>,
+    #[serde(default)]
+    pub conditions: Vec<NamedCondition>,
+}
+
+fn required() -> bool {
+    true
+}
+
+impl RuleDefinition {
+    pub fn leap_day(&self) -> LeapDayPolicy {
+        match self.leap_day {
+            LeapDay::Feb28 => LeapDayPolicy::Feb28,
+            LeapDay::Mar1 => LeapDayPolicy::Mar1,
+        }
+    }
+}
+
+/// Whether a normalized `code` is an ISO 3166-1 country code or, with `subdivisions`, an
+/// ISO 3166-2 subdivision code.
+fn is_iso_code(code: &str, subdivisions: bool) -> bool {
+    let mut parts = code.splitn(2, '-');
+    let country = parts.next().unwrap_or_default();
+    country.len() == 2
+        && country.bytes().all(|b| b.is_ascii_uppercase())
+        && parts.next().is_none_or(|subdivision| {
+            subdivisions
+                && (1..=3).contains(&subdivision.len())
+                && subdivision.bytes().all(|b| b.is_ascii_alphanumeric())
+        })
+}
+
+fn jurisdiction_code<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let code = rules::normalize_jurisdiction(&String::deserialize(deserializer)?);
+    if !is_iso_code(&code, true) {
+        return Err(de::Error::custom(format!(
+            "`{}` is not an ISO 3166 country or subdivision code",
+            code
+        )));
+    }
+    Ok(code)
+}
+
+/// An ISO 3166-1 country code, checked one by one so an error points at the bad entry.
+struct CountryCode(String);
+
+impl<'de> Deserialize<'de> for CountryCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = rules::normalize_jurisdiction(&String::deserialize(deserializer)?);
+        if !is_iso_code(&code, false) {
+            return Err(de::Error::custom(format!(
+                "`{}` is not an ISO 3166-1 country code",
+                code
+            )));
+        }
+        Ok(CountryCode(code))
+    }
+}
+
+fn country_codes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    let codes = Vec::<CountryCode>::deserialize(deserializer)?;
+    Ok(codes.into_iter().map(|CountryCode(code)| code).collect())
+}
+
+fn optional_date<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<NaiveDate>, D::Error> {
+    deserializer.deserialize_any(DateVisitor).map(Some)
+}
+
+/// Reads a `YYYY-MM-DD` string, or a native TOML date.
+struct DateVisitor;
+
+impl<'de> Visitor<'de> for DateVisitor {
+    type Value = NaiveDate;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a YYYY-MM-DD date")
+    }
+
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<NaiveDate, E> {
+        dates::parse_iso_date(text)
+            .map_err(|_| E::custom(format!("`{}` is not a YYYY-MM-DD date", text)))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<NaiveDate, A::Error> {
+        match map.next_key::<String>()? {
+            Some(key) if key == TOML_DATETIME_KEY => self.visit_str(&map.next_value::<String>()?),
+            _ => Err(de::Error::invalid_type(de::Unexpected::Map, &self)),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DefinitionFile {
+    rules: Vec<RuleDefinition>,
+}
+
+/// Parses and validates a rule definition file.
+///
+/// Shape, types, fact names, operators and comparisons of a date with a number are all
+/// checked while parsing, so the error points at the offending value.
+pub fn parse(text: &str, format: RuleFormat) -> Result<Vec<RuleDefinition>, DefinitionError> {
+    let file: DefinitionFile = match format {
+        RuleFormat::Json => {
+            let mut deserializer = serde_json::Deserializer::from_str(text);
+            let file = serde_path_to_error::deserialize(&mut deserializer)
+                .map_err(|error| json_error(error.path().to_string(), error.inner()))?;
+            deserializer
+                .end()
+                .map_err(|error| json_error(".".to_string(), &error))?;
+            file
+        }
+        RuleFormat::Toml => {
+            let syntax = |error: toml::de::Error| {
+                let located = DefinitionError::at(".".to_string(), error.message().to_string());
+                match error.span() {
+                    Some(span) => located.located(text, span.start),
+                    None => located,
+                }
+            };
+            let deserializer = toml::Deserializer::parse(text).map_err(syntax)?;
+            serde_path_to_error::deserialize(deserializer).map_err(|error| {
+                let located = DefinitionError::at(
+                    error.path().to_string(),
+                    error.inner().message().to_string(),
+                );
+                match error.inner().span() {
+                    Some(span) => located.located(text, span.start),
+                    None => located,
+                }
+            })?
+        }
+    };
+    validate(&file.rules)?;
+    Ok(file.rules)
+}
+
+fn json_error(path: String, error: &serde_json::Error) -> DefinitionError {
+    // serde_json appends the position to its messages; it is reported separately here.
+    let message = error.to_string();
+    let message = match message.rfind(" at line ") {
+        Some(index) => message[..index].to_string(),
+        None => message,
+    };
+    DefinitionError {
+        message,
+        path,
+        line: Some(error.line()),
+        column: Some(error.column()),
+    }
+}
+
+/// Checks what the types alone do not: ids are unique, no two rules for a jurisdiction take
+/// effect on the same day, ages stay within [`MAX_AGE`], pre-registration does not open after
+/// the voting age, no `all` or `any` is empty, and no offset runs past the calendar.
+fn validate(definitions: &[RuleDefinition]) -> Result<(), DefinitionError> {
+    for (index, definition) in definitions.iter().enumerate() {
+        let earlier = &definitions[..index];
+        if i32::from(definition.voting_age) > MAX_AGE {
+            return Err(DefinitionError::at(
+                format!("rules[{}].votingAge", index),
+                format!(
+                    "voting age {} is above the maximum of {}",
+                    definition.voting_age, MAX_AGE
+                ),
+            ));
+        }
+        if let Some(age) = definition.preregistration_age {
+            if age > definition.voting_age {
+                return Err(DefinitionError::at(
+                    format!("rules[{}].preregistrationAge", index),
+                    format!(
+                        "pre-registration age {} is above the voting age {}",
+                        age, definition.voting_age
+                    ),
+                ));
+            }
+        }
+        if let Some(id) = &definition.id {
+            if earlier.iter().any(|other| other.id.as_ref() == Some(id)) {
+                return Err(DefinitionError::at(
+                    format!("rules[{}].id", index),
+                    format!("duplicate rule id `{}`", id),
+                ));
+            }
+        }
+        if earlier.iter().any(|other| {
+            other.jurisdiction == definition.jurisdiction
+                && other.effective_from == definition.effective_from
+        }) {
+            return Err(DefinitionError::at(
+                format!("rules[{}].effectiveFrom", index),
+                format!(
+                    "another rule for `{}` already takes effect {}",
+                    definition.jurisdiction,
+                    definition
+                        .effective_from
+                        .map_or("from the start".to_string(), |date| format!("on {}", date))
+                ),
+            ));
+        }
+        for (position, condition) in definition.conditions.iter().enumerate() {
+            let names = &definition.conditions[..position];
+            if names.iter().any(|other| other.name == condition.name) {
+                return Err(DefinitionError::at(
+                    format!("rules[{}].conditions[{}].name", index, position),
+                    format!("duplicate condition name `{}`", condition.name),
+                ));
+            }
+            condition
+                .require
+                .validate(format!("rules[{}].conditions[{}].require", index, position))?;
+        }
+    }
+    Ok(())
+}
+
+/// Parses `text` and puts its rules in force, replacing the whole history of every
+/// jurisdiction it defines. Returns the ids of the loaded rules.
+pub fn load(text: &str, format: RuleFormat) -> Result<Vec<String>, DefinitionError> {
+    let loaded: Vec<VotingAgeRule> = parse(text, format)?
+        .iter()
+        .map(VotingAgeRule::from_definition)
+        .collect();
+    let ids = loaded.iter().map(VotingAgeRule::id).collect();
+    rules::register(loaded);
+    Ok(ids)
+}
+
+/// Rules loaded from JSON or TOML definitions, in force alongside the built-in ones.
+#[wasm_bindgen]
+pub struct RuleDefinitions;
+
+#[wasm_bindgen]
+impl RuleDefinitions {
+    /// Loads rule definitions, returning the ids of the loaded rules.
+    ///
+    /// Throws a `DefinitionError` `{ message, path, line, column }` and loads nothing if any
+    /// rule is invalid.
+    pub fn load(text: &str, format: RuleFormat) -> Result<Vec<String>, JsValue> {
+        load(text, format).map_err(|error| js::to_js(&error).unwrap_or_else(JsValue::from))
+    }
+
+    /// Validates rule definitions without loading them; returns the error, or `undefined`.
+    pub fn validate(text: &str, format: RuleFormat) -> Result<JsValue, JsError> {
+        match parse(text, format) {
+            Ok(_) => Ok(JsValue::UNDEFINED),
+            Err(error) => js::to_js(&error),
+        }
+    }
+
+    /// Drops every loaded rule, going back to the built-in ones.
+    pub fn reset() {
+        rules::reset();
+    }
+
+    /// Ids of the loaded rules.
+    pub fn loaded() -> Vec<String> {
+        rules::registered()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn json_error(text: &str) -> DefinitionError {
+        parse(text, RuleFormat::Json).unwrap_err()
+    }
+
+    fn rule_error(rule: &str) -> DefinitionError {
+        json_error(&format!(
+            r#"{{"rules": [{{"jurisdiction": "XG", {}}}]}}"#,
+            rule
+        ))
+    }
+
+    #[test]
+    fn parses_json_and_toml_alike() {
+        let json = parse(
+            r#"{"rules": [{"jurisdiction": "xg_1", "effectiveFrom": "2020-01-01", "votingAge": 16,
+                "leapDay": "feb28", "otherCitizenships": ["ie"],
+                "conditions": [{"name": "adult", "require": {"compare": {"left": {"fact": "age"}, "op": ">=", "right": 16}}}]}]}"#,
+            RuleFormat::Json,
+        )
+        .unwrap();
+        let toml = parse(
+            r#"
+[[rules]]
+jurisdiction = "XG-1"
+effectiveFrom = 2020-01-01
+votingAge = 16
+leapDay = "feb28"
+otherCitizenships = ["IE"]
+
+[[rules.conditions]]
+name = "adult"
+require = { compare = { left = { fact = "age" }, op = ">=", right = 16 } }
+"#,
+            RuleFormat::Toml,
+        )
+        .unwrap();
+        assert_eq!(json, toml);
+        assert_eq!(json[0].jurisdiction, "XG-1");
+        assert_eq!(json[0].other_citizenships, ["IE"]);
+        assert_eq!(json[0].leap_day(), LeapDayPolicy::Feb28);
+        assert_eq!(json[0].conditions[0].require.to_string(), "age >= 16");
+    }
+
+    #[test]
+    fn locates_json_errors() {
+        let error = json_error(
+            "{\"rules\": [\n  {\"jurisdiction\": \"XG\", \"votingAge\": 16,\n   \"conditions\": [{\"name\": \"n\", \"require\": {\"compare\": {\"left\": 1, \"op\": \"=>\", \"right\": 2}}}]}\n]}",
+        );
+        assert_eq!(error.path, "rules[0].conditions[0].require.compare.op");
+        assert_eq!((error.line, error.column), (Some(3), Some(77)));
+        assert!(!error.message.contains(" at line "));
+
+        let error = json_error(r#"{"rules": [{"jurisdiction": "XG", "votingAge": 16}]} x"#);
+        assert_eq!(error.path, ".");
+        assert_eq!(error.line, Some(1));
+    }
+
+    #[test]
+    fn locates_toml_errors() {
+        let error = parse(
+            "[[rules]]\njurisdiction = \"XG\"\nvotingAge = \"sixteen\"\n",
+            RuleFormat::Toml,
+        )
+        .unwrap_err();
+        assert_eq!(error.path, "rules[0].votingAge");
+        assert_eq!((error.line, error.column), (Some(3), Some(13)));
+
+        let error = parse("[[rules]\n", RuleFormat::Toml).unwrap_err();
+        assert_eq!(error.path, ".");
+        assert_eq!(error.line, Some(1));
+    }
+
+    #[test]
+    fn rejects_dates_compared_with_numbers() {
+        let error = rule_error(
+            r#""votingAge": 16, "conditions": [{"name": "n", "require": {"compare": {"left": {"fact": "on"}, "op": "<", "right": 16}}}]"#,
+        );
+        assert_eq!(error.path, "rules[0].conditions[0].require.compare");
+        assert!(error
+            .message
+            .contains("one is a date and the other a number"));
+    }
+
+    #[test]
+    fn rejects_bad_codes_where_they_are_written() {
+        let error = rule_error(r#""votingAge": 16, "otherCitizenships": ["IE", "GBR"]"#);
+        assert_eq!(error.path, "rules[0].otherCitizenships[1]");
+        assert_eq!(error.message, "`GBR` is not an ISO 3166-1 country code");
+
+        let error = rule_error(r#""votingAge": 16, "otherCitizenships": ["GB-SCT"]"#);
+        assert_eq!(error.path, "rules[0].otherCitizenships[0]");
+
+        let error = json_error(r#"{"rules": [{"jurisdiction": "X", "votingAge": 16}]}"#);
+        assert_eq!(error.path, "rules[0].jurisdiction");
+    }
+
+    #[test]
+    fn rejects_preregistration_after_the_voting_age() {
+        let error = rule_error(r#""votingAge": 16, "preregistrationAge": 17"#);
+        assert_eq!(error.path, "rules[0].preregistrationAge");
+        assert_eq!(
+            error.message,
+            "pre-registration age 17 is above the voting age 16"
+        );
+        assert!(parse(
+            r#"{"rules": [{"jurisdiction": "XG", "votingAge": 16, "preregistrationAge": 16}]}"#,
+            RuleFormat::Json
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn rejects_voting_ages_past_the_maximum() {
+        let error = rule_error(r#""votingAge": 200"#);
+        assert_eq!(error.path, "rules[0].votingAge");
+        assert_eq!(error.message, "voting age 200 is above the maximum of 150");
+    }
+
+    #[test]
+    fn rejects_offsets_past_the_calendar() {
+        let error = rule_error(
+            r#""votingAge": 16, "conditions": [{"name": "n", "require": {"compare": {"left": "on", "op": ">=", "right": {"fact": "dateOfBirth", "offset": {"years": 2147483647}}}}}]"#,
+        );
+        assert_eq!(
+            error.path,
+            "rules[0].conditions[0].require.compare.right.offset"
+        );
+        assert_eq!(error.message, "offset is outside the supported calendar");
+    }
+
+    #[test]
+    fn rejects_empty_groups_at_any_depth() {
+        let error =
+            rule_error(r#""votingAge": 16, "conditions": [{"name": "n", "require": {"all": []}}]"#);
+        assert_eq!(error.path, "rules[0].conditions[0].require.all");
+        assert_eq!(error.message, "`all` needs at least one condition");
+
+        let error = rule_error(
+            r#""votingAge": 16, "conditions": [
+                {"name": "a", "require": {"registered": true}},
+                {"name": "b", "require": {"all": [{"registered": true}, {"not": {"any": []}}]}}
+            ]"#,
+        );
+        assert_eq!(error.path, "rules[0].conditions[1].require.all[1].not.any");
+    }
+
+    #[test]
+    fn rejects_duplicates() {
+        let error = json_error(
+            r#"{"rules": [{"jurisdiction": "XG", "votingAge": 16}, {"jurisdiction": "xg", "votingAge": 18}]}"#,
+        );
+        assert_eq!(error.path, "rules[1].effectiveFrom");
+
+        let error = rule_error(
+            r#""votingAge": 16, "conditions": [{"name": "n", "require": {"registered": true}}, {"name": "n", "require": {"registered": false}}]"#,
+        );
+        assert_eq!(error.path, "rules[0].conditions[1].name");
+    }
+
+    #[test]
+    fn conditions_are_three_valued() {
+        let rules = parse(
+            r#"{"rules": [{"jurisdiction": "XG", "votingAge": 16, "conditions": [
+                {"name": "n", "require": {"any": [{"citizenOf": ["IE"]}, {"not": {"registered": true}}]}}
+            ]}]}"#,
+            RuleFormat::Json,
+        )
+        .unwrap();
+        let condition = &rules[0].conditions[0].require;
+        let outcome = |voter: Voter| {
+            let facts = Facts {
+                voter: &voter,
+                on: None,
+                leap_day: LeapDayPolicy::Mar1,
+            };
+            condition.evaluate(&facts).unwrap()
+        };
+        assert_eq!(outcome(Voter::default()), Outcome::Unknown);
+        let irish = Voter {
+            citizenships: Some(vec!["ie".to_string()]),
+            ..Voter::default()
+        };
+        assert_eq!(outcome(irish), Outcome::Pass);
+        let registered = Voter {
+            citizenships: Some(vec!["FR".to_string()]),
+            registered: Some(true),
+            ..Voter::default()
+        };
+        assert_eq!(outcome(registered), Outcome::Fail);
+    }
+}
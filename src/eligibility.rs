@@ -0,0 +1,651 @@
+use ::std::cmp::Ordering;
+
+use chrono::NaiveDate;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::catalog::{self, Arg};
+use crate::convictions::{self, Conviction, VotingRights};
+use crate::criteria::{Outcome, Voter};
+use crate::dates;
+use crate::declarative::Facts;
+use crate::error::EligibilityError;
+use crate::escape::{self, OutputContext};
+use crate::js;
+use crate::locale;
+use crate::registration::{self, RegistrationStatus};
+use crate::rules::{self, VotingAgeRule};
+use crate::trace::{Comparison, DecisionTrace, Steps, Subject, TraceInputs, TracedRule};
+
+#[wasm_bindgen(typescript_custom_section)]
+const ELIGIBILITY_RESULT_JSON: &'static str = r#"
+export interface EligibilityResultJSON {
+  status: "Eligible" | "NotYetEligible" | "JustEligible";
+  age: number;
+  threshold: number;
+  yearsUntilEligible: number;
+  registrationStatus: "Ineligible" | "PreRegistrationEligible" | "RegistrationEligible" | "VotingEligible";
+  linkedElection: LinkedElectionJSON | null;
+  votingRights: VotingRightsJSON | null;
+  unmetConditions: string[];
+  jurisdiction: string;
+  ruleId: string;
+}
+
+export interface LinkedElectionJSON {
+  id: string;
+  date: string;
+  age: number;
+  qualifies: boolean;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "EligibilityResultJSON")]
+    pub type EligibilityResultJson;
+
+    #[wasm_bindgen(typescript_type = "LinkedElectionJSON")]
+    pub type LinkedElectionJson;
+}
+
+/// Where an age falls relative to the voting age.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum EligibilityStatus {
+    /// Past the voting age (`Ordering::Greater`).
+    Eligible,
+    /// Below the voting age (`Ordering::Less`).
+    NotYetEligible,
+    /// Exactly at the voting age (`Ordering::Equal`).
+    JustEligible,
+}
+
+impl EligibilityStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EligibilityStatus::Eligible => "Eligible",
+            EligibilityStatus::NotYetEligible => "NotYetEligible",
+            EligibilityStatus::JustEligible => "JustEligible",
+        }
+    }
+
+    /// Catalog id of the message describing this status.
+    pub fn message_id(self) -> &'static str {
+        match self {
+            EligibilityStatus::Eligible => "eligible",
+            EligibilityStatus::NotYetEligible => "not-eligible",
+            EligibilityStatus::JustEligible => "just-eligible",
+        }
+    }
+}
+
+impl From<Ordering> for EligibilityStatus {
+    fn from(ordering: Ordering) -> Self {
+        match ordering {
+            Ordering::Greater => EligibilityStatus::Eligible,
+            Ordering::Less => EligibilityStatus::NotYetEligible,
+            Ordering::Equal => EligibilityStatus::JustEligible,
+        }
+    }
+}
+
+/// Structured outcome of an eligibility check.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EligibilityResult {
+    status: EligibilityStatus,
+    age: i32,
+    threshold: u8,
+    years_until_eligible: u32,
+    registration_status: RegistrationStatus,
+    linked_election: Option<LinkedElection>,
+    voting_rights: Option<VotingRights>,
+    unmet_conditions: Vec<String>,
+    jurisdiction: String,
+    rule_id: String,
+    #[serde(skip)]
+    rule: VotingAgeRule,
+    #[serde(skip)]
+    steps: Steps,
+}
+
+/// The later election whose date decided a linked-election check, such as the general
+/// election a 17-year-old's primary vote depends on.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LinkedElection {
+    id: String,
+    #[serde(serialize_with = "dates::serialize_iso")]
+    date: NaiveDate,
+    age: i32,
+    qualifies: bool,
+}
+
+#[wasm_bindgen]
+impl LinkedElection {
+    #[wasm_bindgen(getter)]
+    pub fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    /// The linked election's date, as `YYYY-MM-DD`.
+    #[wasm_bindgen(getter)]
+    pub fn date(&self) -> String {
+        self.date.to_string()
+    }
+
+    /// Age reached by the linked election.
+    #[wasm_bindgen(getter)]
+    pub fn age(&self) -> i32 {
+        self.age
+    }
+
+    /// Whether that age meets the voting age, making the voter eligible.
+    #[wasm_bindgen(getter)]
+    pub fn qualifies(&self) -> bool {
+        self.qualifies
+    }
+
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json(&self) -> Result<LinkedElectionJson, JsError> {
+        Ok(js::to_js(self)?.unchecked_into())
+    }
+}
+
+#[wasm_bindgen]
+impl EligibilityResult {
+    #[wasm_bindgen(getter)]
+    pub fn status(&self) -> EligibilityStatus {
+        self.status
+    }
+
+    /// Old enough, meeting the rule's own conditions, and not barred by a criminal record
+    /// when one was checked.
+    #[wasm_bindgen(getter)]
+    pub fn eligible(&self) -> bool {
+        self.status != EligibilityStatus::NotYetEligible
+            && self.unmet_conditions.is_empty()
+            && self
+                .voting_rights
+                .as_ref()
+                .is_none_or(VotingRights::can_vote)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn age(&self) -> i32 {
+        self.age
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    #[wasm_bindgen(getter, js_name = yearsUntilEligible)]
+    pub fn years_until_eligible(&self) -> u32 {
+        self.years_until_eligible
+    }
+
+    /// Whether this person can pre-register, register or vote.
+    #[wasm_bindgen(getter, js_name = registrationStatus)]
+    pub fn registration_status(&self) -> RegistrationStatus {
+        self.registration_status
+    }
+
+    /// The linked election that was considered, when the rule ties this election to another.
+    #[wasm_bindgen(getter, js_name = linkedElection)]
+    pub fn linked_election(&self) -> Option<LinkedElection> {
+        self.linked_election.clone()
+    }
+
+    /// Names of the loaded rule's conditions that the inputs fail.
+    #[wasm_bindgen(getter, js_name = unmetConditions)]
+    pub fn unmet_conditions(&self) -> Vec<String> {
+        self.unmet_conditions.clone()
+    }
+
+    /// Voting rights under the conviction rules, when a criminal record was checked.
+    #[wasm_bindgen(getter, js_name = votingRights)]
+    pub fn voting_rights(&self) -> Option<VotingRights> {
+        self.voting_rights.clone()
+    }
+
+    /// Why a linked election decided the result, in `locale`, or `undefined` if none did.
+    #[wasm_bindgen(js_name = explanation)]
+    pub fn explanation(&self, locale: &str) -> Option<String> {
+        let linked = self.linked_election.as_ref()?;
+        let date = linked.date.to_string();
+        let id = if linked.qualifies {
+            "linked-election-eligible"
+        } else {
+            "linked-election-not-eligible"
+        };
+        Some(catalog::render(
+            locale,
+            id,
+            &[
+                ("age", Arg::Number(self.age.into())),
+                ("threshold", Arg::Number(self.threshold.into())),
+                ("linkedAge", Arg::Number(linked.age.into())),
+                ("date", Arg::Text(&date)),
+            ],
+            Some(linked.age.into()),
+        ))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn jurisdiction(&self) -> String {
+        self.jurisdiction.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = ruleId)]
+    pub fn rule_id(&self) -> String {
+        self.rule_id.clone()
+    }
+
+    /// The voting-age rule the check applied, with its effective dates and leap-day policy.
+    #[wasm_bindgen(getter)]
+    pub fn rule(&self) -> VotingAgeRule {
+        self.rule.clone()
+    }
+
+    /// How this result was reached: inputs, rules consulted, comparisons made and verdict.
+    #[wasm_bindgen(getter)]
+    pub fn trace(&self) -> DecisionTrace {
+        DecisionTrace::new(
+            &self.steps,
+            self.status,
+            self.eligible(),
+            self.voting_rights.as_ref().map(VotingRights::status),
+            &self.unmet_conditions,
+        )
+    }
+
+    /// The English sentence `age_comparator` returns for this result.
+    #[wasm_bindgen(getter)]
+    pub fn message(&self) -> String {
+        self.localized_message(locale::DEFAULT_LOCALE)
+    }
+
+    /// This result as a sentence in `locale`, with numbers in the locale's own digits.
+    #[wasm_bindgen(js_name = localizedMessage)]
+    pub fn localized_message(&self, locale: &str) -> String {
+        self.message_for(locale, OutputContext::Plain)
+    }
+
+    /// This result as a sentence in `locale`, escaped for `context`.
+    ///
+    /// Below the voting age, someone who can already pre-register or register is told so
+    /// instead of only that they cannot vote yet.
+    #[wasm_bindgen(js_name = messageFor)]
+    pub fn message_for(&self, locale: &str, context: OutputContext) -> String {
+        let id = match (self.status, self.registration_status) {
+            (
+                EligibilityStatus::NotYetEligible,
+                status @ (RegistrationStatus::PreRegistrationEligible
+                | RegistrationStatus::RegistrationEligible),
+            ) => status.message_id(),
+            (status, _) => status.message_id(),
+        };
+        escape::render(
+            locale,
+            id,
+            &[
+                ("age", Arg::Number(self.age.into())),
+                ("threshold", Arg::Number(self.threshold.into())),
+            ],
+            Some(self.age.into()),
+            context,
+        )
+    }
+
+    /// "You can vote in 3 years, on your 18th birthday." in `locale`, or `undefined` once eligible.
+    #[wasm_bindgen(js_name = yearsUntilEligibleMessage)]
+    pub fn years_until_eligible_message(&self, locale: &str) -> Option<String> {
+        if self.years_until_eligible == 0 {
+            return None;
+        }
+        Some(catalog::render(
+            locale,
+            "years-until-eligible",
+            &[
+                ("years", Arg::Number(self.years_until_eligible.into())),
+                ("threshold", Arg::Number(self.threshold.into())),
+            ],
+            Some(self.years_until_eligible.into()),
+        ))
+    }
+
+    /// The registration status as a sentence in `locale`: "You are 16 and can pre-register…".
+    #[wasm_bindgen(js_name = registrationMessage)]
+    pub fn registration_message(&self, locale: &str) -> String {
+        catalog::render(
+            locale,
+            self.registration_status.message_id(),
+            &[
+                ("age", Arg::Number(self.age.into())),
+                ("threshold", Arg::Number(self.threshold.into())),
+            ],
+            Some(self.age.into()),
+        )
+    }
+
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json(&self) -> Result<EligibilityResultJson, JsError> {
+        Ok(js::to_js(self)?.unchecked_into())
+    }
+}
+
+impl EligibilityResult {
+    /// Attaches the outcome of a criminal-record check, which `eligible` then takes into account.
+    pub fn with_voting_rights(mut self, rights: VotingRights) -> Self {
+        self.steps.rules.push(TracedRule::conviction(&rights));
+        self.voting_rights = Some(rights);
+        self
+    }
+
+    fn new(
+        age: i32,
+        voter: Voter,
+        inputs: TraceInputs,
+        comparison: Comparison,
+        registration_status: RegistrationStatus,
+        rule: &VotingAgeRule,
+    ) -> Result<Self, EligibilityError> {
+        let threshold = rule.voting_age();
+        let facts = Facts {
+            voter: &voter,
+            on: inputs.on,
+            leap_day: rule.leap_day(),
+        };
+        // Only a definite failure counts; conditions on facts not given here stay open.
+        let mut unmet_conditions = Vec::new();
+        for condition in rule.conditions() {
+            if condition.require.evaluate(&facts)? == Outcome::Fail {
+                unmet_conditions.push(condition.name.clone());
+            }
+        }
+        Ok(EligibilityResult {
+            status: comparison.ordering.into(),
+            age,
+            threshold,
+            years_until_eligible: (i32::from(threshold) - age).max(0) as u32,
+            registration_status,
+            linked_election: None,
+            voting_rights: None,
+            unmet_conditions,
+            jurisdiction: rule.jurisdiction(),
+            rule_id: rule.id(),
+            rule: rule.clone(),
+            steps: Steps {
+                inputs,
+                rules: vec![TracedRule::voting_age(rule)],
+                comparisons: vec![comparison],
+            },
+        })
+    }
+}
+
+/// Compares `age` against `rule`'s voting age.
+pub fn evaluate_age(age: i32, rule: &VotingAgeRule) -> Result<EligibilityResult, EligibilityError> {
+    let inputs = TraceInputs {
+        age: Some(age),
+        ..TraceInputs::default()
+    };
+    let voter = Voter {
+        age: Some(age),
+        ..Voter::default()
+    };
+    EligibilityResult::new(
+        age,
+        voter,
+        inputs,
+        Comparison::ages(age, rule.voting_age()),
+        registration::status_for_age(age, rule),
+        rule,
+    )
+}
+
+/// Compares the age reached on `election` by someone born on `dob` against `rule`.
+///
+/// The comparison is made on dates, so turning the voting age on election day is `JustEligible`.
+pub fn evaluate_dob(
+    dob: NaiveDate,
+    election: NaiveDate,
+    rule: &VotingAgeRule,
+) -> Result<EligibilityResult, EligibilityError> {
+    let age = dates::age_on(dob, election, rule.leap_day())?;
+    let eligible_from = dates::anniversary(dob, rule.voting_age().into(), rule.leap_day())?;
+    let inputs = TraceInputs {
+        age: None,
+        date_of_birth: Some(dob),
+        on: Some(election),
+    };
+    let voter = Voter {
+        date_of_birth: Some(dob.to_string()),
+        ..Voter::default()
+    };
+    EligibilityResult::new(
+        age,
+        voter,
+        inputs,
+        Comparison::dates(Subject::ElectionDate, election, eligible_from),
+        registration::status_on(dob, election, rule)?,
+        rule,
+    )
+}
+
+/// Like [`evaluate_dob`], but for a primary tied to the general election `linked` (with id
+/// `linked_id`): under a rule that allows it, someone below the voting age on `election`
+/// can vote if they reach it by `linked`.
+pub fn evaluate_linked(
+    dob: NaiveDate,
+    election: NaiveDate,
+    linked_id: &str,
+    linked: NaiveDate,
+    rule: &VotingAgeRule,
+) -> Result<EligibilityResult, EligibilityError> {
+    let mut result = evaluate_dob(dob, election, rule)?;
+    if result.eligible() || !rule.primary_by_general() || linked < election {
+        return Ok(result);
+    }
+    let eligible_from = dates::anniversary(dob, rule.voting_age().into(), rule.leap_day())?;
+    let comparison = Comparison::dates(Subject::LinkedElectionDate, linked, eligible_from);
+    let ordering = comparison.ordering;
+    result.steps.comparisons.push(comparison);
+    let qualifies = ordering != Ordering::Less;
+    if qualifies {
+        result.status = ordering.into();
+        result.years_until_eligible = 0;
+        result.registration_status = RegistrationStatus::VotingEligible;
+    }
+    result.linked_election = Some(LinkedElection {
+        id: linked_id.to_string(),
+        date: linked,
+        age: dates::age_on(dob, linked, rule.leap_day())?,
+        qualifies,
+    });
+    Ok(result)
+}
+
+/// Compares `age` against the voting age of `jurisdiction`.
+pub fn check_in(jurisdiction: &str, age: i32) -> Result<EligibilityResult, EligibilityError> {
+    evaluate_age(age, &rules::lookup(jurisdiction)?)
+}
+
+/// Compares the age reached on `election` against the voting age `jurisdiction` had then.
+pub fn check_on(
+    jurisdiction: &str,
+    dob: NaiveDate,
+    election: NaiveDate,
+) -> Result<EligibilityResult, EligibilityError> {
+    evaluate_dob(dob, election, &rules::lookup_as_of(jurisdiction, election)?)
+}
+
+/// [`check_on`] that also applies `jurisdiction`'s conviction rules to `convictions`.
+pub fn check_with_record(
+    jurisdiction: &str,
+    dob: NaiveDate,
+    convictions: &[Conviction],
+    on: NaiveDate,
+) -> Result<EligibilityResult, EligibilityError> {
+    let rights = convictions::voting_rights(jurisdiction, convictions, on)?;
+    Ok(check_on(jurisdiction, dob, on)?.with_voting_rights(rights))
+}
+
+/// Structured form of `age_comparator`, against the default voting age.
+#[wasm_bindgen]
+pub fn check_age(age: i8) -> EligibilityResult {
+    evaluate_age(age.into(), &VotingAgeRule::default_rule())
+        .expect("the default rule has no conditions")
+}
+
+#[wasm_bindgen]
+pub fn age_comparator_in(jurisdiction: &str, age: i8) -> Result<EligibilityResult, JsError> {
+    Ok(check_in(jurisdiction, age.into())?)
+}
+
+/// Checks eligibility from a date of birth on an election date.
+///
+/// Both dates may be ISO-8601 strings or JS `Date` objects.
+#[wasm_bindgen]
+pub fn age_comparator_on(
+    jurisdiction: &str,
+    date_of_birth: JsValue,
+    election_date: JsValue,
+) -> Result<EligibilityResult, JsError> {
+    let dob = dates::date_from_js(&date_of_birth)?;
+    let election = dates::date_from_js(&election_date)?;
+    Ok(check_on(jurisdiction, dob, election)?)
+}
+
+/// Describes what someone aged `age` may do towards voting in `jurisdiction`: pre-register,
+/// register or vote.
+#[wasm_bindgen]
+pub fn registration_comparator(jurisdiction: &str, age: i8) -> Result<String, JsError> {
+    Ok(check_in(jurisdiction, age.into())?.registration_message(locale::DEFAULT_LOCALE))
+}
+
+/// `age_comparator_on` that also applies the jurisdiction's conviction rules.
+///
+/// `convictions` is an array of `Conviction` objects; `eligible` is false while the vote is
+/// suspended or lost, and `votingRights` says why and until when.
+#[wasm_bindgen]
+pub fn age_comparator_with_record(
+    jurisdiction: &str,
+    date_of_birth: JsValue,
+    convictions: JsValue,
+    on: JsValue,
+) -> Result<EligibilityResult, JsError> {
+    let convictions: Vec<Conviction> = js::options_from_js(convictions)?;
+    let dob = dates::date_from_js(&date_of_birth)?;
+    let on = dates::date_from_js(&on)?;
+    Ok(check_with_record(jurisdiction, dob, &convictions, on)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dates::LeapDayPolicy;
+    use crate::declarative::{self, RuleFormat};
+
+    fn date(text: &str) -> NaiveDate {
+        dates::parse_iso_date(text).unwrap()
+    }
+
+    fn load_conditions(jurisdiction: &str, conditions: &str) -> VotingAgeRule {
+        let rules = format!(
+            r#"{{"rules": [{{"jurisdiction": "{}", "votingAge": 18, "conditions": {}}}]}}"#,
+            jurisdiction, conditions
+        );
+        declarative::load(&rules, RuleFormat::Json).unwrap();
+        rules::lookup(jurisdiction).unwrap()
+    }
+
+    #[test]
+    fn compares_ages_with_the_voting_age() {
+        let statuses: Vec<_> = [17, 18, 19].map(|age| check_age(age).status()).to_vec();
+        assert_eq!(
+            statuses,
+            [
+                EligibilityStatus::NotYetEligible,
+                EligibilityStatus::JustEligible,
+                EligibilityStatus::Eligible
+            ]
+        );
+        assert_eq!(check_age(15).years_until_eligible(), 3);
+        assert_eq!(check_age(17).message(), "You are 17 Not Eligible To Vote");
+    }
+
+    #[test]
+    fn returns_the_rule_it_applied() {
+        let rule = check_on("GB-SCT", date("2000-01-01"), date("2016-05-05"))
+            .unwrap()
+            .rule();
+        assert_eq!(rule.jurisdiction(), "GB-SCT");
+        assert_eq!(rule.voting_age(), 16);
+        assert_eq!(rule.effective_from(), Some(date("2015-06-18")));
+        assert_eq!(rule.effective_to(), None);
+        assert_eq!(rule.leap_day(), LeapDayPolicy::Mar1);
+    }
+
+    #[test]
+    fn reports_registration_ahead_of_the_voting_age() {
+        let result = check_in("GB", 16).unwrap();
+        assert_eq!(
+            result.registration_status(),
+            RegistrationStatus::PreRegistrationEligible
+        );
+        assert!(!result.eligible());
+        assert_eq!(
+            result.message(),
+            "You are 16 and can pre-register to vote; you can vote at 18."
+        );
+        assert_eq!(
+            check_age(16).registration_status(),
+            RegistrationStatus::Ineligible
+        );
+    }
+
+    #[test]
+    fn lists_only_conditions_that_definitely_fail() {
+        let rule = load_conditions(
+            "XE",
+            r#"[
+                {"name": "adult", "require": {"compare": {"left": {"fact": "age"}, "op": ">=", "right": 21}}},
+                {"name": "resident", "require": {"compare": {"left": {"fact": "residentSince"}, "op": "<=", "right": {"fact": "on"}}}}
+            ]"#,
+        );
+        let result = evaluate_dob(date("2006-05-01"), date("2026-05-01"), &rule).unwrap();
+        assert_eq!(result.unmet_conditions(), ["adult"]);
+        assert!(!result.eligible());
+
+        let result = evaluate_dob(date("2000-05-01"), date("2026-05-01"), &rule).unwrap();
+        assert!(result.unmet_conditions().is_empty());
+        assert!(result.eligible());
+    }
+
+    #[test]
+    fn a_condition_that_cannot_be_evaluated_is_an_error() {
+        let rule = load_conditions(
+            "XF",
+            r#"[{"name": "resident", "require": {"compare": {"left": {"fact": "residentSince"}, "op": "<=", "right": "2026-01-01"}}}]"#,
+        );
+        let voter = Voter {
+            age: Some(30),
+            resident_since: Some("last spring".to_string()),
+            ..Voter::default()
+        };
+        let result = EligibilityResult::new(
+            30,
+            voter,
+            TraceInputs::default(),
+            Comparison::ages(30, rule.voting_age()),
+            RegistrationStatus::VotingEligible,
+            &rule,
+        );
+        assert!(matches!(result, Err(EligibilityError::InvalidDate(_))));
+    }
+}
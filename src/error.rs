@@ -0,0 +1,88 @@
+use std::fmt;
+
+use chrono::NaiveDate;
+
+use crate::validation::RowError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EligibilityError {
+    UnknownJurisdiction(String),
+    InvalidDate(String),
+    DateOutOfRange { date: NaiveDate, years: u32 },
+    BornAfterReference { dob: NaiveDate, on: NaiveDate },
+    MissingColumn(String),
+    InvalidDelimiter(char),
+    Csv(String),
+    InvalidRow(Box<RowError>),
+    StreamFinished,
+    InvalidTimestamp(String),
+    UnknownTimeZone(String),
+    InvalidElection { index: usize, message: String },
+    Json(String),
+    NoRuleInForce { jurisdiction: String, on: NaiveDate },
+}
+
+impl fmt::Display for EligibilityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EligibilityError::UnknownJurisdiction(code) => {
+                write!(f, "unknown jurisdiction `{}`", code)
+            }
+            EligibilityError::InvalidDate(value) => {
+                write!(f, "`{}` is not an ISO-8601 date", value)
+            }
+            EligibilityError::DateOutOfRange { date, years } => {
+                write!(
+                    f,
+                    "{} years after {} is outside the supported calendar",
+                    years, date
+                )
+            }
+            EligibilityError::BornAfterReference { dob, on } => {
+                write!(
+                    f,
+                    "date of birth {} is after the reference date {}",
+                    dob, on
+                )
+            }
+            EligibilityError::MissingColumn(column) => write!(f, "missing column `{}`", column),
+            EligibilityError::InvalidDelimiter(delimiter) => {
+                write!(
+                    f,
+                    "delimiter `{}` is not a single ASCII character",
+                    delimiter
+                )
+            }
+            EligibilityError::Csv(message) => write!(f, "malformed CSV: {}", message),
+            EligibilityError::InvalidRow(error) => write!(f, "{}", error),
+            EligibilityError::StreamFinished => write!(f, "the stream has already finished"),
+            EligibilityError::InvalidTimestamp(value) => {
+                write!(f, "`{}` is not a timestamp, Date or RFC 3339 string", value)
+            }
+            EligibilityError::UnknownTimeZone(zone) => {
+                write!(f, "`{}` is not an IANA time zone", zone)
+            }
+            EligibilityError::InvalidElection { index, message } => {
+                write!(f, "election {}: {}", index, message)
+            }
+            EligibilityError::Json(message) => write!(f, "malformed JSON: {}", message),
+            EligibilityError::NoRuleInForce { jurisdiction, on } => {
+                write!(f, "no rule for `{}` was in force on {}", jurisdiction, on)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EligibilityError {}
+
+impl From<csv::Error> for EligibilityError {
+    fn from(error: csv::Error) -> Self {
+        EligibilityError::Csv(error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for EligibilityError {
+    fn from(error: serde_json::Error) -> Self {
+        EligibilityError::Json(error.to_string())
+    }
+}
@@ -0,0 +1,214 @@
+use std::fmt::Write;
+
+use wasm_bindgen::prelude::*;
+
+use crate::catalog::{self, Arg};
+
+/// Where a rendered message is going to be inserted.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputContext {
+    /// Text nodes, `textContent`, terminals: nothing is escaped.
+    #[default]
+    Plain,
+    /// HTML element content, e.g. `innerHTML`.
+    Html,
+    /// A quoted HTML attribute value.
+    Attribute,
+    /// CommonMark text.
+    Markdown,
+    /// A complete JSON string literal, quotes included.
+    Json,
+}
+
+/// Escapes `text` for element content.
+pub fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Escapes `text` for an attribute value: every ASCII character but letters and digits
+/// becomes a character reference, so it is safe even in an unquoted attribute.
+pub fn escape_attribute(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() || !c.is_ascii() {
+            out.push(c);
+        } else {
+            let _ = write!(out, "&#x{:02X};", c as u32);
+        }
+    }
+    out
+}
+
+/// Backslash-escapes CommonMark punctuation, including `<` so no raw HTML gets through.
+pub fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii_punctuation() {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Writes `text` as a JSON string literal that is also safe inside a `<script>` element.
+pub fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '<' | '>' | '&' | '\u{2028}' | '\u{2029}' => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            _ => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Escapes an interpolated value for `context`. JSON is handled on the whole message instead.
+fn escape_value(text: &str, context: OutputContext) -> String {
+    match context {
+        OutputContext::Plain | OutputContext::Json => text.to_string(),
+        OutputContext::Html => escape_html(text),
+        OutputContext::Attribute => escape_attribute(text),
+        OutputContext::Markdown => escape_markdown(text),
+    }
+}
+
+/// Renders a catalog message for `context`, escaping interpolated text but not the message.
+pub fn render(
+    locale: &str,
+    id: &str,
+    args: &[(&str, Arg)],
+    count: Option<i64>,
+    context: OutputContext,
+) -> String {
+    let message =
+        catalog::render_with(locale, id, args, count, &|text| escape_value(text, context));
+    match context {
+        OutputContext::Json => json_string(&message),
+        _ => message,
+    }
+}
+
+/// HTML that is known to be safe to insert with `innerHTML`.
+///
+/// JS code can only get one from an escaping function, so escaped and unescaped strings
+/// cannot be mixed up by accident.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SafeHtml {
+    html: String,
+}
+
+impl SafeHtml {
+    /// Wraps markup the caller guarantees is already safe.
+    pub(crate) fn from_trusted(html: String) -> Self {
+        SafeHtml { html }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.html
+    }
+}
+
+#[wasm_bindgen]
+impl SafeHtml {
+    /// Escapes plain `text` into HTML.
+    pub fn escape(text: &str) -> SafeHtml {
+        SafeHtml::from_trusted(escape_html(text))
+    }
+
+    /// Appends another piece of safe HTML.
+    pub fn concat(&self, other: &SafeHtml) -> SafeHtml {
+        SafeHtml::from_trusted(format!("{}{}", self.html, other.html))
+    }
+
+    /// Appends plain `text`, escaping it.
+    #[wasm_bindgen(js_name = concatText)]
+    pub fn concat_text(&self, text: &str) -> SafeHtml {
+        SafeHtml::from_trusted(format!("{}{}", self.html, escape_html(text)))
+    }
+
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_html(&self) -> String {
+        self.html.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAME: &str = r#"<img src=x onerror="alert('1')"> & *co*"#;
+
+    fn greeting(context: OutputContext) -> String {
+        render(
+            "en",
+            "greeting",
+            &[("name", Arg::Text(NAME))],
+            None,
+            context,
+        )
+    }
+
+    #[test]
+    fn escapes_interpolated_values_for_each_context() {
+        assert_eq!(greeting(OutputContext::Plain), format!("Hello, {}!", NAME));
+        assert_eq!(
+            greeting(OutputContext::Html),
+            "Hello, &lt;img src=x onerror=&quot;alert(&#39;1&#39;)&quot;&gt; &amp; *co*!"
+        );
+        assert_eq!(
+            greeting(OutputContext::Markdown),
+            r#"Hello, \<img src\=x onerror\=\"alert\(\'1\'\)\"\> \& \*co\*!"#
+        );
+        assert!(greeting(OutputContext::Attribute)
+            .starts_with("Hello, &#x3C;img&#x20;src&#x3D;x&#x20;"));
+    }
+
+    #[test]
+    fn writes_json_that_is_safe_in_a_script_element() {
+        assert_eq!(
+            greeting(OutputContext::Json),
+            r#""Hello, \u003cimg src=x onerror=\"alert('1')\"\u003e \u0026 *co*!""#
+        );
+        assert_eq!(json_string("a\nb\u{2028}\u{7}"), r#""a\nb\u2028\u0007""#);
+    }
+
+    #[test]
+    fn attribute_escaping_keeps_letters_digits_and_non_ascii() {
+        assert_eq!(escape_attribute("Zoë 18"), "Zoë&#x20;18");
+        assert_eq!(escape_attribute("a`b"), "a&#x60;b");
+    }
+
+    #[test]
+    fn safe_html_escapes_what_it_is_given() {
+        let html = SafeHtml::escape("<b>")
+            .concat(&SafeHtml::from_trusted("<br>".to_string()))
+            .concat_text("&");
+        assert_eq!(html.as_str(), "&lt;b&gt;<br>&amp;");
+    }
+}
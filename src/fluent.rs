@@ -0,0 +1,391 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use fluent_syntax::ast::{
+    Entry, Expression, InlineExpression, Pattern, PatternElement, Resource, VariantKey,
+};
+use fluent_syntax::parser;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::catalog::Arg;
+use crate::js;
+use crate::locale::LanguageTag;
+use crate::numbers;
+use crate::plural;
+
+/// Deepest chain of message and term references followed while rendering.
+const MAX_DEPTH: usize = 16;
+
+thread_local! {
+    /// Loaded resources per normalized locale, newest last.
+    static RESOURCES: RefCell<HashMap<String, Vec<Resource<String>>>> = RefCell::new(HashMap::new());
+}
+
+/// A syntax error in an FTL resource, with a 1-based line and column.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FtlError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl FtlError {
+    fn new(source: &str, error: &parser::ParserError) -> Self {
+        let before = &source[..error.pos.start.min(source.len())];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        FtlError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message: error.kind.to_string(),
+        }
+    }
+}
+
+/// Why `load` rejected a resource.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LoadError {
+    pub message: String,
+    pub errors: Vec<FtlError>,
+}
+
+/// Parses `source` and adds it to `locale`, overriding messages loaded earlier.
+///
+/// A resource with any syntax error is rejected as a whole.
+pub fn load(locale: &str, source: &str) -> Result<(), LoadError> {
+    let locale = LanguageTag::parse(locale)
+        .ok_or_else(|| LoadError {
+            message: format!("`{}` is not a BCP-47 language tag", locale),
+            errors: Vec::new(),
+        })?
+        .to_string();
+    let resource = parser::parse(source.to_string()).map_err(|(_, errors)| LoadError {
+        message: format!("{} syntax error(s) in FTL for `{}`", errors.len(), locale),
+        errors: errors
+            .iter()
+            .map(|error| FtlError::new(source, error))
+            .collect(),
+    })?;
+    RESOURCES.with(|resources| {
+        resources
+            .borrow_mut()
+            .entry(locale)
+            .or_default()
+            .push(resource)
+    });
+    Ok(())
+}
+
+/// Drops the resources loaded for `locale`, or for every locale.
+pub fn clear(locale: Option<&str>) {
+    RESOURCES.with(|resources| match locale {
+        Some(locale) => {
+            let key = LanguageTag::parse(locale).map(|tag| tag.to_string());
+            if let Some(key) = key {
+                resources.borrow_mut().remove(&key);
+            }
+        }
+        None => resources.borrow_mut().clear(),
+    });
+}
+
+/// Locales that have at least one loaded resource.
+pub fn loaded_locales() -> Vec<String> {
+    RESOURCES.with(|resources| resources.borrow().keys().cloned().collect())
+}
+
+/// Whether the resources loaded for exactly `locale` give message `id` a value.
+pub fn has_message(locale: &str, id: &str) -> bool {
+    RESOURCES.with(|resources| {
+        let resources = resources.borrow();
+        let Some(resources) = resources.get(locale) else {
+            return false;
+        };
+        let scope = Scope {
+            resources,
+            language: locale.split('-').next().unwrap_or_default(),
+            display_locale: locale,
+            args: &[],
+            escape: &|text| text.to_string(),
+        };
+        scope.message(id).is_some()
+    })
+}
+
+/// Renders message `id` from the resources loaded for exactly `locale`, if it has a value.
+///
+/// Numbers are written in `display_locale`'s digits and text arguments pass through `escape`.
+pub fn render(
+    locale: &str,
+    id: &str,
+    args: &[(&str, Arg)],
+    display_locale: &str,
+    escape: &dyn Fn(&str) -> String,
+) -> Option<String> {
+    RESOURCES.with(|resources| {
+        let resources = resources.borrow();
+        let scope = Scope {
+            resources: resources.get(locale)?,
+            language: locale.split('-').next().unwrap_or_default(),
+            display_locale,
+            args,
+            escape,
+        };
+        let pattern = scope.message(id)?;
+        let mut out = String::new();
+        scope.write_pattern(&mut out, pattern, 0);
+        Some(out)
+    })
+}
+
+struct Scope<'a> {
+    resources: &'a [Resource<String>],
+    language: &'a str,
+    display_locale: &'a str,
+    args: &'a [(&'a str, Arg<'a>)],
+    escape: &'a dyn Fn(&str) -> String,
+}
+
+impl<'a> Scope<'a> {
+    fn entries(&self) -> impl Iterator<Item = &'a Entry<String>> {
+        self.resources
+            .iter()
+            .rev()
+            .flat_map(|resource| resource.body.iter().rev())
+    }
+
+    fn message(&self, id: &str) -> Option<&'a Pattern<String>> {
+        self.entries().find_map(|entry| match entry {
+            Entry::Message(message) if message.id.name == id => message.value.as_ref(),
+            _ => None,
+        })
+    }
+
+    fn term(&self, id: &str) -> Option<&'a Pattern<String>> {
+        self.entries().find_map(|entry| match entry {
+            Entry::Term(term) if term.id.name == id => Some(&term.value),
+            _ => None,
+        })
+    }
+
+    fn arg(&self, name: &str) -> Option<Arg<'a>> {
+        self.args
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| *value)
+    }
+
+    fn write_pattern(&self, out: &mut String, pattern: &Pattern<String>, depth: usize) {
+        for element in &pattern.elements {
+            match element {
+                PatternElement::TextElement { value } => out.push_str(value),
+                PatternElement::Placeable { expression } => {
+                    self.write_expression(out, expression, depth)
+                }
+            }
+        }
+    }
+
+    fn write_expression(&self, out: &mut String, expression: &Expression<String>, depth: usize) {
+        match expression {
+            Expression::Inline(inline) => self.write_inline(out, inline, depth),
+            Expression::Select { selector, variants } => {
+                let chosen = variants
+                    .iter()
+                    .find(|variant| self.matches(selector, &variant.key))
+                    .or_else(|| variants.iter().find(|variant| variant.default));
+                if let Some(variant) = chosen {
+                    self.write_pattern(out, &variant.value, depth);
+                }
+            }
+        }
+    }
+
+    fn write_inline(&self, out: &mut String, inline: &InlineExpression<String>, depth: usize) {
+        match inline {
+            InlineExpression::StringLiteral { value } => out.push_str(value),
+            InlineExpression::NumberLiteral { value } => out.push_str(value),
+            InlineExpression::VariableReference { id } => match self.arg(&id.name) {
+                Some(Arg::Text(text)) => out.push_str(&(self.escape)(text)),
+                Some(Arg::Number(n)) => {
+                    out.push_str(&numbers::format_integer(n, self.display_locale))
+                }
+                None => {
+                    let _ = write!(out, "{{${}}}", id.name);
+                }
+            },
+            InlineExpression::FunctionReference { id, arguments } if id.name == "NUMBER" => {
+                if let Some(argument) = arguments.positional.first() {
+                    self.write_inline(out, argument, depth);
+                }
+            }
+            InlineExpression::FunctionReference { id, .. } => {
+                let _ = write!(out, "{{{}()}}", id.name);
+            }
+            InlineExpression::MessageReference { id, .. } => {
+                match self.message(&id.name).filter(|_| depth < MAX_DEPTH) {
+                    Some(pattern) => self.write_pattern(out, pattern, depth + 1),
+                    None => {
+                        let _ = write!(out, "{{{}}}", id.name);
+                    }
+                }
+            }
+            InlineExpression::TermReference { id, .. } => {
+                match self.term(&id.name).filter(|_| depth < MAX_DEPTH) {
+                    Some(pattern) => self.write_pattern(out, pattern, depth + 1),
+                    None => {
+                        let _ = write!(out, "{{-{}}}", id.name);
+                    }
+                }
+            }
+            InlineExpression::Placeable { expression } => {
+                self.write_expression(out, expression, depth)
+            }
+        }
+    }
+
+    /// Whether a select expression's `selector` picks the variant keyed `key`.
+    fn matches(&self, selector: &InlineExpression<String>, key: &VariantKey<String>) -> bool {
+        let value = match selector {
+            InlineExpression::VariableReference { id } => self.arg(&id.name),
+            InlineExpression::FunctionReference { id, arguments } if id.name == "NUMBER" => {
+                match arguments.positional.first() {
+                    Some(InlineExpression::VariableReference { id }) => self.arg(&id.name),
+                    _ => None,
+                }
+            }
+            InlineExpression::StringLiteral { value } => Some(Arg::Text(value)),
+            InlineExpression::NumberLiteral { value } => value.parse().ok().map(Arg::Number),
+            _ => None,
+        };
+        match (value, key) {
+            (Some(Arg::Number(n)), VariantKey::NumberLiteral { value }) => {
+                value.parse::<i64>() == Ok(n)
+            }
+            (Some(Arg::Number(n)), VariantKey::Identifier { name }) => {
+                plural::cardinal(self.language, n).as_str() == name
+            }
+            (Some(Arg::Text(text)), VariantKey::Identifier { name }) => text == name,
+            _ => false,
+        }
+    }
+}
+
+/// Runtime-loaded Project Fluent messages used by `greet` and `age_comparator`.
+#[wasm_bindgen]
+pub struct Messages;
+
+#[wasm_bindgen]
+impl Messages {
+    /// Loads an FTL resource for `locale`; throws `{ message, errors: [{ line, column, message }] }`.
+    pub fn load(locale: &str, ftl: &str) -> Result<(), JsValue> {
+        load(locale, ftl).map_err(|error| js::to_js(&error).unwrap_or_else(JsValue::from))
+    }
+
+    /// Unloads `locale`, or every locale when called without one.
+    pub fn clear(locale: Option<String>) {
+        clear(locale.as_deref());
+    }
+
+    #[wasm_bindgen(js_name = loadedLocales)]
+    pub fn loaded_locales() -> Vec<String> {
+        loaded_locales()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(text: &str) -> String {
+        text.to_string()
+    }
+
+    fn render_plain(locale: &str, id: &str, args: &[(&str, Arg)]) -> Option<String> {
+        render(locale, id, args, locale, &plain)
+    }
+
+    #[test]
+    fn renders_variables_terms_and_references() {
+        load(
+            "en",
+            "-brand = Votey\ngreeting = Hello, { $name }!\nwelcome = { greeting } Welcome to { -brand }.\n",
+        )
+        .unwrap();
+        assert_eq!(
+            render_plain("en", "welcome", &[("name", Arg::Text("Ada"))]).as_deref(),
+            Some("Hello, Ada! Welcome to Votey.")
+        );
+        assert_eq!(
+            render_plain("en", "greeting", &[]).as_deref(),
+            Some("Hello, {$name}!")
+        );
+        assert_eq!(render_plain("en", "missing", &[]), None);
+        assert_eq!(render_plain("fr", "greeting", &[]), None);
+    }
+
+    #[test]
+    fn selects_plural_variants_in_the_locales_digits() {
+        load(
+            "ar",
+            "years = { $n ->\n    [0] لا سنوات\n    [two] سنتان\n    [few] { $n } سنوات\n   *[other] { $n } سنة\n}\n",
+        )
+        .unwrap();
+        let years = |n| render_plain("ar", "years", &[("n", Arg::Number(n))]).unwrap();
+        assert_eq!(years(0), "لا سنوات");
+        assert_eq!(years(2), "سنتان");
+        assert_eq!(years(3), "٣ سنوات");
+        assert_eq!(years(11), "١١ سنة");
+    }
+
+    #[test]
+    fn escapes_text_arguments_only() {
+        load("en", "hi = <b>{ $name }</b> is { $age }").unwrap();
+        let upper = |text: &str| text.to_uppercase();
+        assert_eq!(
+            render(
+                "en",
+                "hi",
+                &[("name", Arg::Text("ada")), ("age", Arg::Number(18))],
+                "en",
+                &upper
+            )
+            .as_deref(),
+            Some("<b>ADA</b> is 18")
+        );
+    }
+
+    #[test]
+    fn later_resources_override_earlier_ones() {
+        load("de", "hi = Hallo").unwrap();
+        load("de_de", "hi = Servus").unwrap();
+        load("de", "hi = Moin").unwrap();
+        assert_eq!(render_plain("de", "hi", &[]).as_deref(), Some("Moin"));
+        assert!(has_message("de", "hi"));
+        assert!(!has_message("de-DE", "bye"));
+
+        clear(Some("DE"));
+        assert_eq!(loaded_locales(), ["de-DE"]);
+        clear(None);
+        assert!(loaded_locales().is_empty());
+    }
+
+    #[test]
+    fn reports_syntax_errors_with_positions() {
+        let error = load("en", "ok = fine\nbroken = { $name $age }\n").unwrap_err();
+        assert_eq!(error.errors.len(), 1);
+        assert_eq!((error.errors[0].line, error.errors[0].column), (2, 18));
+        assert!(!has_message("en", "ok"));
+
+        let error = load("not a tag", "ok = fine").unwrap_err();
+        assert!(error.errors.is_empty());
+        assert_eq!(error.message, "`not a tag` is not a BCP-47 language tag");
+    }
+
+    #[test]
+    fn stops_following_cyclic_references() {
+        load("en", "a = { b }\nb = { a }\n").unwrap();
+        assert_eq!(render_plain("en", "a", &[]).as_deref(), Some("{b}"));
+    }
+}
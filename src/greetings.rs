@@ -0,0 +1,230 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
+use chrono_tz::Tz;
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+
+use crate::catalog::{self, Arg};
+use crate::dates;
+use crate::error::EligibilityError;
+use crate::js;
+use crate::locale;
+use crate::names;
+use crate::rules::{self, VotingAgeRule};
+
+/// Options accepted by `greet_at`. Every field is optional.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GreetAtOptions {
+    pub locale: Option<String>,
+    /// ISO-8601 date of birth; enables birthday greetings.
+    pub date_of_birth: Option<String>,
+    /// Jurisdiction whose voting age and leap-day policy birthdays are checked against.
+    pub jurisdiction: Option<String>,
+}
+
+/// Part of the day a greeting is chosen for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayPeriod {
+    /// 05:00 to 11:59.
+    Morning,
+    /// 12:00 to 17:59.
+    Afternoon,
+    /// 18:00 to 04:59.
+    Evening,
+}
+
+impl DayPeriod {
+    pub fn of(hour: u32) -> Self {
+        match hour {
+            5..=11 => DayPeriod::Morning,
+            12..=17 => DayPeriod::Afternoon,
+            _ => DayPeriod::Evening,
+        }
+    }
+
+    fn message_id(self) -> &'static str {
+        match self {
+            DayPeriod::Morning => "greeting-morning",
+            DayPeriod::Afternoon => "greeting-afternoon",
+            DayPeriod::Evening => "greeting-evening",
+        }
+    }
+}
+
+/// Greets `name` for a local date and time.
+///
+/// Birthdays (when the date of birth is known) win over New Year's Day, which wins over the
+/// time of day. Turning the voting age is called out on the birthday itself.
+pub fn greet_local(
+    name: &str,
+    local: NaiveDateTime,
+    options: &GreetAtOptions,
+) -> Result<String, EligibilityError> {
+    let locale = options.locale.as_deref().unwrap_or(locale::DEFAULT_LOCALE);
+    let name = names::normalize(name);
+    let name = Arg::Text(name.as_str());
+    let today = local.date();
+
+    if let Some(dob) = &options.date_of_birth {
+        let dob = dates::parse_iso_date(dob)?;
+        let rule = match &options.jurisdiction {
+            Some(code) => rules::lookup_as_of(code, today)?,
+            None => VotingAgeRule::default_rule(),
+        };
+        if let Some(age) = birthday_age(dob, today, &rule) {
+            let id = if age == i32::from(rule.voting_age()) {
+                "greeting-birthday-vote"
+            } else {
+                "greeting-birthday"
+            };
+            return Ok(catalog::render(
+                locale,
+                id,
+                &[("name", name), ("age", Arg::Number(age.into()))],
+                Some(age.into()),
+            ));
+        }
+    }
+
+    let id = if (today.month(), today.day()) == (1, 1) {
+        "greeting-new-year"
+    } else {
+        DayPeriod::of(local.hour()).message_id()
+    };
+    Ok(catalog::render(locale, id, &[("name", name)], None))
+}
+
+/// The age turned on `today`, if it is the birthday under `rule`'s leap-day policy.
+fn birthday_age(dob: NaiveDate, today: NaiveDate, rule: &VotingAgeRule) -> Option<i32> {
+    let age = dates::age_on(dob, today, rule.leap_day()).ok()?;
+    (age > 0 && dates::anniversary(dob, age as u32, rule.leap_day()).ok()? == today).then_some(age)
+}
+
+/// Greets `name` for an instant as seen in an IANA time zone.
+///
+/// `timestamp` may be epoch milliseconds, a `Date` or an RFC 3339 string. The time zone
+/// database is compiled in, so this works offline.
+#[wasm_bindgen]
+pub fn greet_at(
+    name: &str,
+    timestamp: JsValue,
+    tz: &str,
+    options: JsValue,
+) -> Result<String, JsError> {
+    let options: GreetAtOptions = js::options_from_js(options)?;
+    let zone: Tz = tz
+        .parse()
+        .map_err(|_| EligibilityError::UnknownTimeZone(tz.to_string()))?;
+    let local = dates::instant_from_js(&timestamp)?
+        .with_timezone(&zone)
+        .naive_local();
+    Ok(greet_local(name, local, &options)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(text: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn options(dob: Option<&str>, jurisdiction: Option<&str>) -> GreetAtOptions {
+        GreetAtOptions {
+            locale: None,
+            date_of_birth: dob.map(str::to_string),
+            jurisdiction: jurisdiction.map(str::to_string),
+        }
+    }
+
+    fn greet(local: &str, options: &GreetAtOptions) -> String {
+        greet_local(" Ada  Lovelace", at(local), options).unwrap()
+    }
+
+    #[test]
+    fn greets_by_time_of_day() {
+        let plain = GreetAtOptions::default();
+        assert_eq!(
+            greet("2026-05-01 04:59", &plain),
+            "Good evening, Ada Lovelace!"
+        );
+        assert_eq!(
+            greet("2026-05-01 05:00", &plain),
+            "Good morning, Ada Lovelace!"
+        );
+        assert_eq!(
+            greet("2026-05-01 12:00", &plain),
+            "Good afternoon, Ada Lovelace!"
+        );
+        assert_eq!(
+            greet("2026-05-01 18:00", &plain),
+            "Good evening, Ada Lovelace!"
+        );
+        assert_eq!(
+            greet("2026-01-01 09:00", &plain),
+            "Happy New Year, Ada Lovelace!"
+        );
+    }
+
+    #[test]
+    fn birthdays_win_and_call_out_the_voting_age() {
+        let adult = options(Some("2000-01-01"), None);
+        assert_eq!(
+            greet("2026-01-01 09:00", &adult),
+            "Happy 26th birthday, Ada Lovelace!"
+        );
+        let austrian = options(Some("2010-05-01"), Some("AT"));
+        assert_eq!(
+            greet("2026-05-01 09:00", &austrian),
+            "Happy 16th birthday, Ada Lovelace, you can now vote!"
+        );
+        assert_eq!(
+            greet("2026-05-01 09:00", &options(Some("2010-05-01"), None)),
+            "Happy 16th birthday, Ada Lovelace!"
+        );
+    }
+
+    #[test]
+    fn leap_day_birthdays_follow_the_jurisdiction() {
+        let march = options(Some("2008-02-29"), None);
+        assert_eq!(
+            greet("2026-02-28 09:00", &march),
+            "Good morning, Ada Lovelace!"
+        );
+        assert_eq!(
+            greet("2026-03-01 09:00", &march),
+            "Happy 18th birthday, Ada Lovelace, you can now vote!"
+        );
+        let taiwan = options(Some("2008-02-29"), Some("TW"));
+        assert_eq!(
+            greet("2026-02-28 09:00", &taiwan),
+            "Happy 18th birthday, Ada Lovelace!"
+        );
+    }
+
+    #[test]
+    fn greets_in_the_requested_locale() {
+        let german = GreetAtOptions {
+            locale: Some("de-AT".to_string()),
+            ..GreetAtOptions::default()
+        };
+        assert_eq!(
+            greet("2026-05-01 20:00", &german),
+            "Guten Abend, Ada Lovelace!"
+        );
+    }
+
+    #[test]
+    fn rejects_bad_dates_and_jurisdictions() {
+        let now = at("2026-05-01 09:00");
+        assert!(matches!(
+            greet_local("Ada", now, &options(Some("yesterday"), None)),
+            Err(EligibilityError::InvalidDate(_))
+        ));
+        assert!(matches!(
+            greet_local("Ada", now, &options(Some("2000-01-01"), Some("ZZ"))),
+            Err(EligibilityError::UnknownJurisdiction(_))
+        ));
+        assert_eq!(DayPeriod::of(23), DayPeriod::Evening);
+    }
+}
@@ -14,20 +14,213 @@
 // }
 
 ///////////////////////////////////////////// WASM
-use ::std::cmp::Ordering;
+use catalog::Arg;
 use wasm_bindgen::prelude::*;
 
+mod calendar;
+mod catalog;
+mod convictions;
+mod countdown;
+mod criteria;
+mod dates;
+mod declarative;
+mod eligibility;
+mod error;
+mod escape;
+mod fluent;
+mod greetings;
+mod js;
+mod locale;
+mod names;
+mod numbers;
+mod plural;
+mod registration;
+mod roll;
+mod rules;
+mod stream;
+mod template;
+mod trace;
+mod validation;
+
+pub use calendar::{Election, ElectionCalendar, ElectionType};
+pub use convictions::{
+    check_voting_rights, voting_rights, Conviction, ConvictionRules, DisenfranchisementPolicy,
+    RightsStatus, SentenceStatus, VotingRights,
+};
+pub use countdown::{countdown, countdown_in, eligibility_countdown, Countdown};
+pub use criteria::{
+    assess, assess_voter, AgeCriterion, Assessment, CitizenshipCriterion, ConditionCriterion,
+    Context, ConvictionCriterion, Criterion, CriterionResult, Outcome, Pipeline,
+    RegistrationCriterion, ResidencyCriterion, Verdict, Voter,
+};
+pub use dates::LeapDayPolicy;
+pub use declarative::{
+    load as load_rule_definitions, parse as parse_rule_definitions, Comparator, Condition,
+    DefinitionError, Fact, Facts, NamedCondition, Offset, Operand, RuleDefinition, RuleDefinitions,
+    RuleFormat,
+};
+pub use eligibility::{
+    check_age, check_in, check_on, check_with_record, EligibilityResult, EligibilityStatus,
+};
+pub use error::EligibilityError;
+pub use escape::{OutputContext, SafeHtml};
+pub use fluent::{load as load_messages, FtlError, LoadError, Messages};
+pub use greetings::{greet_at, greet_local, DayPeriod, GreetAtOptions};
+pub use locale::LanguageTag;
+pub use names::{
+    normalize_name, parse_name, GreetingStyle, NameChange, NameChangeKind, NameConvention,
+    NormalizedName, ParsedName,
+};
+pub use plural::PluralCategory;
+pub use registration::RegistrationStatus;
+pub use roll::{annotate_roll, check_roll, RollOptions, RollReport, RollSummary, RowResult};
+pub use rules::{rule_history, rule_in_force, VotingAgeRule};
+pub use stream::RollStream;
+pub use template::{register as register_template, Template, TemplateError, Templates};
+pub use trace::{Comparison, DecisionTrace, RuleKind, Subject, TraceInputs, TracedRule};
+pub use validation::{ErrorMode, RowError, ValidationErrorKind};
+
 #[wasm_bindgen]
 pub fn greet(name: &str) -> String {
-    format!("Hello, {}!", name)
+    greet_localized(name, locale::DEFAULT_LOCALE)
+}
+
+/// `greet` in the given BCP-47 locale, falling back through e.g. `pt-BR` → `pt` → `en`.
+#[wasm_bindgen]
+pub fn greet_localized(name: &str, locale: &str) -> String {
+    let name = names::normalize(name);
+    catalog::render(
+        locale,
+        "greeting",
+        &[("name", Arg::Text(name.as_str()))],
+        None,
+    )
+}
+
+/// Greets the right parts of a full name: `Dear Dr. García López` or `Hello, María!`.
+#[wasm_bindgen]
+pub fn greet_styled(name: &str, style: GreetingStyle) -> String {
+    greet_styled_localized(name, style, locale::DEFAULT_LOCALE)
+}
+
+/// `greet_styled` in the given BCP-47 locale.
+///
+/// The locale picks both the message and how the name is split (`es` names carry two
+/// family names, `zh`/`ja`/`ko` names put the family name first).
+#[wasm_bindgen]
+pub fn greet_styled_localized(name: &str, style: GreetingStyle, locale: &str) -> String {
+    let parsed = names::parse(name, NameConvention::for_locale(locale));
+    let (id, name) = match style {
+        GreetingStyle::Formal => ("greeting-formal", parsed.formal()),
+        GreetingStyle::Informal => ("greeting", parsed.informal()),
+    };
+    catalog::render(locale, id, &[("name", Arg::Text(&name))], None)
+}
+
+/// `greet` escaped for the place its output will be inserted.
+#[wasm_bindgen]
+pub fn greet_for(name: &str, context: OutputContext) -> String {
+    let name = names::normalize(name);
+    escape::render(
+        locale::DEFAULT_LOCALE,
+        "greeting",
+        &[("name", Arg::Text(name.as_str()))],
+        None,
+        context,
+    )
+}
+
+/// `greet` as HTML, with `name` escaped.
+#[wasm_bindgen]
+pub fn greet_html(name: &str) -> SafeHtml {
+    SafeHtml::from_trusted(greet_for(name, OutputContext::Html))
+}
+
+/// Locales that have a built-in catalog or loaded Fluent messages.
+///
+/// With a message `id` such as `"eligible"`, only the locales that translate it; the others
+/// would render it in English.
+#[wasm_bindgen]
+pub fn available_locales(id: Option<String>) -> Vec<String> {
+    match id {
+        Some(id) => catalog::locales_with(&id),
+        None => catalog::locales(),
+    }
+}
+
+/// Ordinal of `n` in `locale`: `18th`, `18.`, `18e`, `第18`.
+#[wasm_bindgen]
+pub fn format_ordinal(n: i32, locale: &str) -> String {
+    numbers::format_ordinal(n.into(), locale)
+}
+
+/// CLDR cardinal plural category of `n` in `locale`: `"one"`, `"few"`, `"other"`, ...
+#[wasm_bindgen]
+pub fn plural_category(n: i32, locale: &str) -> String {
+    plural::category(n.into(), locale).as_str().to_string()
+}
+
+/// CLDR ordinal plural category of `n` in `locale`, e.g. `"two"` for English 22 (`22nd`).
+#[wasm_bindgen]
+pub fn ordinal_category(n: i32, locale: &str) -> String {
+    plural::ordinal_category(n.into(), locale)
+        .as_str()
+        .to_string()
 }
 
 #[wasm_bindgen]
 pub fn age_comparator(age: i8) -> String {
-    let eligible_age = 18;
-    match age.cmp(&eligible_age) {
-        Ordering::Greater => format!("You are {} Eligible To Vote", age),
-        Ordering::Less => format!("You are {} Not Eligible To Vote", age),
-        Ordering::Equal => format!("Congrats You gained the Rights to Vote").to_string(),
+    check_age(age).message()
+}
+
+/// `age_comparator` in the given BCP-47 locale.
+#[wasm_bindgen]
+pub fn age_comparator_localized(age: i8, locale: &str) -> String {
+    check_age(age).localized_message(locale)
+}
+
+/// `age_comparator` escaped for the place its output will be inserted.
+#[wasm_bindgen]
+pub fn age_comparator_for(age: i8, context: OutputContext) -> String {
+    check_age(age).message_for(locale::DEFAULT_LOCALE, context)
+}
+
+/// `age_comparator` as HTML.
+#[wasm_bindgen]
+pub fn age_comparator_html(age: i8) -> SafeHtml {
+    SafeHtml::from_trusted(age_comparator_for(age, OutputContext::Html))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn greet_uses_the_whole_name_unless_styled() {
+        assert_eq!(greet("  María  García "), "Hello, María García!");
+        assert_eq!(
+            greet_styled("Dr. María García", GreetingStyle::Informal),
+            "Hello, María!"
+        );
+        assert_eq!(
+            greet_styled_localized("Dr. María García López", GreetingStyle::Formal, "es"),
+            "Dear Dr. García López"
+        );
+        assert_eq!(
+            greet_styled_localized("王小明", GreetingStyle::Informal, "zh"),
+            "你好，小明！"
+        );
+    }
+
+    #[test]
+    fn age_comparator_messages_say_when_someone_can_preregister() {
+        let message = |jurisdiction, age| check_in(jurisdiction, age).unwrap().message();
+        assert_eq!(age_comparator(17), "You are 17 Not Eligible To Vote");
+        assert_eq!(
+            message("GB", 16),
+            "You are 16 and can pre-register to vote; you can vote at 18."
+        );
+        assert_eq!(message("GB", 15), "You are 15 Not Eligible To Vote");
+        assert_eq!(message("GB", 19), "You are 19 Eligible To Vote");
     }
 }
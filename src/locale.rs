@@ -0,0 +1,141 @@
+use std::fmt;
+
+/// Locale every fallback chain ends in.
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// The language, script and region subtags of a BCP-47 language tag.
+///
+/// Variants, extensions and private-use subtags are accepted but not kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageTag {
+    pub language: String,
+    pub script: Option<String>,
+    pub region: Option<String>,
+}
+
+impl LanguageTag {
+    /// Parses tags such as `pt-BR`, `zh_Hant_TW` or `sr-Latn`, normalizing subtag case.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let mut subtags = tag.trim().split(['-', '_']).peekable();
+
+        let language = subtags.next()?;
+        let is_language = matches!(language.len(), 2..=3 | 5..=8)
+            && language.bytes().all(|b| b.is_ascii_alphabetic());
+        if !is_language {
+            return None;
+        }
+
+        let script = subtags
+            .next_if(|s| s.len() == 4 && s.bytes().all(|b| b.is_ascii_alphabetic()))
+            .map(|s| {
+                let (first, rest) = s.split_at(1);
+                first.to_ascii_uppercase() + &rest.to_ascii_lowercase()
+            });
+        let region = subtags
+            .next_if(|s| {
+                (s.len() == 2 && s.bytes().all(|b| b.is_ascii_alphabetic()))
+                    || (s.len() == 3 && s.bytes().all(|b| b.is_ascii_digit()))
+            })
+            .map(|s| s.to_ascii_uppercase());
+
+        // Anything left must at least be well-formed subtags.
+        if subtags
+            .any(|s| s.is_empty() || s.len() > 8 || !s.bytes().all(|b| b.is_ascii_alphanumeric()))
+        {
+            return None;
+        }
+
+        Some(LanguageTag {
+            language: language.to_ascii_lowercase(),
+            script,
+            region,
+        })
+    }
+
+    /// Tags to try, most specific first: `zh-Hant-TW`, `zh-Hant`, `zh-TW`, `zh`, then `en`.
+    pub fn fallback_chain(&self) -> Vec<String> {
+        let language = &self.language;
+        let mut chain = Vec::with_capacity(5);
+        if let (Some(script), Some(region)) = (&self.script, &self.region) {
+            chain.push(format!("{}-{}-{}", language, script, region));
+        }
+        if let Some(script) = &self.script {
+            chain.push(format!("{}-{}", language, script));
+        }
+        if let Some(region) = &self.region {
+            chain.push(format!("{}-{}", language, region));
+        }
+        chain.push(language.clone());
+        if language != DEFAULT_LOCALE {
+            chain.push(DEFAULT_LOCALE.to_string());
+        }
+        chain
+    }
+}
+
+impl fmt::Display for LanguageTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.language)?;
+        if let Some(script) = &self.script {
+            write!(f, "-{}", script)?;
+        }
+        if let Some(region) = &self.region {
+            write!(f, "-{}", region)?;
+        }
+        Ok(())
+    }
+}
+
+/// Fallback chain for a user-supplied locale; malformed tags fall straight back to `en`.
+pub fn fallback_chain(locale: &str) -> Vec<String> {
+    LanguageTag::parse(locale)
+        .map(|tag| tag.fallback_chain())
+        .unwrap_or_else(|| vec![DEFAULT_LOCALE.to_string()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_normalizes_tags() {
+        let tag = LanguageTag::parse("zh_hant_tw").unwrap();
+        assert_eq!(tag.language, "zh");
+        assert_eq!(tag.script.as_deref(), Some("Hant"));
+        assert_eq!(tag.region.as_deref(), Some("TW"));
+        assert_eq!(tag.to_string(), "zh-Hant-TW");
+        assert_eq!(
+            LanguageTag::parse("es-419-u-nu-latn").unwrap().to_string(),
+            "es-419"
+        );
+        assert_eq!(
+            LanguageTag::parse("SR-latn").unwrap().to_string(),
+            "sr-Latn"
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_tags() {
+        for tag in [
+            "",
+            "e",
+            "english language",
+            "en--US",
+            "12",
+            "en-US-toolongsubtag",
+        ] {
+            assert_eq!(LanguageTag::parse(tag), None, "{:?}", tag);
+        }
+    }
+
+    #[test]
+    fn falls_back_to_english() {
+        assert_eq!(
+            fallback_chain("zh-Hant-TW"),
+            ["zh-Hant-TW", "zh-Hant", "zh-TW", "zh", "en"]
+        );
+        assert_eq!(fallback_chain("pt_br"), ["pt-BR", "pt", "en"]);
+        assert_eq!(fallback_chain("en-GB"), ["en-GB", "en"]);
+        assert_eq!(fallback_chain("not a tag"), ["en"]);
+    }
+}
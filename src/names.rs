@@ -0,0 +1,733 @@
+use serde::Serialize;
+use unicode_normalization::UnicodeNormalization;
+use wasm_bindgen::prelude::*;
+
+use crate::js;
+
+/// A step of `normalize_name` that altered the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum NameChangeKind {
+    /// C0/C1 control characters were removed.
+    ControlRemoved,
+    /// Bidi embeddings, overrides, isolates and marks were removed.
+    BidiControlRemoved,
+    /// Zero-width spaces, joiners and the BOM were removed outside words that need them.
+    ZeroWidthRemoved,
+    /// The text was recomposed to NFC.
+    Normalized,
+    /// Runs of whitespace were collapsed to single spaces.
+    WhitespaceCollapsed,
+    /// Leading or trailing whitespace was removed.
+    Trimmed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NameChange {
+    pub kind: NameChangeKind,
+    pub count: usize,
+}
+
+/// Writing systems told apart when looking for confusable names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+    Other,
+}
+
+fn script_of(c: char) -> Option<Script> {
+    if !c.is_alphabetic() {
+        return None;
+    }
+    Some(match c as u32 {
+        0x0041..=0x024F | 0x1E00..=0x1EFF | 0xFF21..=0xFF5A => Script::Latin,
+        0x0370..=0x03FF | 0x1F00..=0x1FFF => Script::Greek,
+        0x0400..=0x052F | 0x1C80..=0x1C8F | 0x2DE0..=0x2DFF | 0xA640..=0xA69F => Script::Cyrillic,
+        _ => Script::Other,
+    })
+}
+
+fn is_bidi_control(c: char) -> bool {
+    matches!(c, '\u{200E}' | '\u{200F}' | '\u{061C}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}')
+}
+
+fn is_zero_width(c: char) -> bool {
+    matches!(c, '\u{200B}' | '\u{2060}' | '\u{FEFF}' | '\u{180E}')
+}
+
+fn is_joiner(c: char) -> bool {
+    matches!(c, '\u{200C}' | '\u{200D}')
+}
+
+/// A name after `normalize_name`, with a record of what was changed.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizedName {
+    value: String,
+    original: String,
+    changes: Vec<NameChange>,
+    /// Words that mix Latin, Greek and Cyrillic letters, e.g. a Cyrillic `а` in `Anna`.
+    confusable_words: Vec<String>,
+}
+
+#[wasm_bindgen]
+impl NormalizedName {
+    #[wasm_bindgen(getter)]
+    pub fn value(&self) -> String {
+        self.value.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn original(&self) -> String {
+        self.original.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn changed(&self) -> bool {
+        !self.changes.is_empty()
+    }
+
+    /// `[{ kind, count }]` for every step that altered the name.
+    #[wasm_bindgen(getter)]
+    pub fn changes(&self) -> Result<JsValue, JsError> {
+        js::to_js(&self.changes)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn confusable(&self) -> bool {
+        !self.confusable_words.is_empty()
+    }
+
+    #[wasm_bindgen(getter, js_name = confusableWords)]
+    pub fn confusable_words(&self) -> Vec<String> {
+        self.confusable_words.clone()
+    }
+
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json(&self) -> Result<JsValue, JsError> {
+        js::to_js(self)
+    }
+}
+
+impl NormalizedName {
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+/// Normalizes a personal name before it is displayed or stored.
+///
+/// Strips control characters, bidi controls and stray zero-width characters (joiners are
+/// kept between letters of scripts that use them), recomposes to NFC, collapses whitespace
+/// and trims. Mixed-script words are reported rather than changed.
+pub fn normalize(name: &str) -> NormalizedName {
+    let mut changes = Vec::new();
+    let mut note = |kind, count| {
+        if count > 0 {
+            changes.push(NameChange { kind, count });
+        }
+    };
+
+    let chars: Vec<char> = name.chars().collect();
+    let (mut controls, mut bidi, mut zero_width) = (0, 0, 0);
+    let mut stripped = String::with_capacity(name.len());
+    for (index, &c) in chars.iter().enumerate() {
+        if is_bidi_control(c) {
+            bidi += 1;
+        } else if is_zero_width(c) {
+            zero_width += 1;
+        } else if is_joiner(c) {
+            let joins_letters = |neighbor: Option<&char>| {
+                neighbor.is_some_and(|&n| !n.is_ascii() && (n.is_alphabetic() || is_mark(n)))
+            };
+            if index > 0
+                && joins_letters(chars.get(index - 1))
+                && joins_letters(chars.get(index + 1))
+            {
+                stripped.push(c);
+            } else {
+                zero_width += 1;
+            }
+        } else if c.is_control() && !c.is_whitespace() {
+            controls += 1;
+        } else {
+            stripped.push(c);
+        }
+    }
+    note(NameChangeKind::ControlRemoved, controls);
+    note(NameChangeKind::BidiControlRemoved, bidi);
+    note(NameChangeKind::ZeroWidthRemoved, zero_width);
+
+    let composed: String = stripped.nfc().collect();
+    note(
+        NameChangeKind::Normalized,
+        usize::from(composed != stripped),
+    );
+
+    let trimmed = composed.trim();
+    note(
+        NameChangeKind::Trimmed,
+        usize::from(trimmed.len() != composed.len()),
+    );
+
+    let words: Vec<&str> = trimmed
+        .split(char::is_whitespace)
+        .filter(|w| !w.is_empty())
+        .collect();
+    let value = words.join(" ");
+    let collapsed = trimmed
+        .split(|c: char| !c.is_whitespace())
+        .filter(|run| !run.is_empty() && *run != " ")
+        .count();
+    note(NameChangeKind::WhitespaceCollapsed, collapsed);
+
+    let confusable_words = words
+        .iter()
+        .filter(|word| {
+            let mut scripts = word
+                .chars()
+                .filter_map(script_of)
+                .filter(|script| *script != Script::Other);
+            let first = scripts.next();
+            scripts.any(|script| Some(script) != first)
+        })
+        .map(|word| word.to_string())
+        .collect();
+
+    NormalizedName {
+        value,
+        original: name.to_string(),
+        changes,
+        confusable_words,
+    }
+}
+
+fn is_mark(c: char) -> bool {
+    use unicode_normalization::char::is_combining_mark;
+    is_combining_mark(c)
+}
+
+/// Normalizes a name the same way `greet` does, reporting what changed.
+#[wasm_bindgen]
+pub fn normalize_name(name: &str) -> NormalizedName {
+    normalize(name)
+}
+
+const HONORIFICS: &[&str] = &[
+    "mr", "mrs", "ms", "miss", "mx", "dr", "prof", "rev", "fr", "sir", "dame", "lord", "lady",
+    "hon", "sr", "sra", "srta", "don", "doña", "dona", "herr", "frau", "mme", "mlle", "m", "dott",
+    "ing", "lic",
+];
+
+const SUFFIXES: &[&str] = &[
+    "jr", "sr", "ii", "iii", "iv", "v", "phd", "md", "esq", "dds", "mba", "qc", "kc",
+];
+
+/// Honorifics and suffixes that are also initials or names (`M Smith`, `Ana V`); they only
+/// count when written with a period or when a given and a family name remain without them.
+const AMBIGUOUS_AFFIXES: &[&str] = &["m", "v", "sr"];
+
+/// Lowercase words that belong to the family name that follows them.
+const PARTICLES: &[&str] = &[
+    "van", "von", "der", "den", "de", "del", "della", "di", "da", "das", "dos", "du", "la", "le",
+    "ten", "ter", "zu", "und", "bin", "ibn", "al", "el", "y",
+];
+
+/// Two-character Chinese family names; any other Han name starts with a one-character family name.
+const CHINESE_COMPOUND_FAMILY_NAMES: &[&str] = &[
+    "欧阳", "歐陽", "司马", "司馬", "诸葛", "諸葛", "上官", "东方", "東方", "皇甫", "尉迟", "尉遲",
+    "公孙", "公孫", "慕容", "夏侯", "长孙", "長孫", "令狐", "宇文", "司徒", "端木",
+];
+
+/// Two-syllable Korean family names; any other Hangul name starts with a one-syllable family name.
+const KOREAN_COMPOUND_FAMILY_NAMES: &[&str] =
+    &["남궁", "선우", "제갈", "황보", "독고", "사공", "서문"];
+
+/// How the parts of a personal name are ordered.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub enum NameConvention {
+    /// Family-first for Han, Hangul and Kana names, Western otherwise.
+    #[default]
+    Auto,
+    /// Given names then one family name, which may start with particles (`van`, `von`, `de`).
+    Western,
+    /// Given names then two family names, e.g. `María García López`.
+    Hispanic,
+    /// Family name first, e.g. `王小明`, `山田 太郎`, `Nagy János`.
+    FamilyFirst,
+}
+
+impl NameConvention {
+    /// Convention implied by a BCP-47 locale.
+    pub fn for_locale(locale: &str) -> Self {
+        match crate::locale::LanguageTag::parse(locale)
+            .as_ref()
+            .map(|tag| tag.language.as_str())
+        {
+            Some("es") => NameConvention::Hispanic,
+            Some("zh" | "ja" | "ko" | "hu") => NameConvention::FamilyFirst,
+            _ => NameConvention::Auto,
+        }
+    }
+}
+
+/// A personal name split into its components.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParsedName {
+    honorific: Option<String>,
+    given: Vec<String>,
+    family: Vec<String>,
+    suffix: Option<String>,
+    convention: NameConvention,
+    /// Set when the family name is written before the given names.
+    family_first: bool,
+}
+
+#[wasm_bindgen]
+impl ParsedName {
+    #[wasm_bindgen(getter)]
+    pub fn honorific(&self) -> Option<String> {
+        self.honorific.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn given(&self) -> Vec<String> {
+        self.given.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn family(&self) -> Vec<String> {
+        self.family.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn suffix(&self) -> Option<String> {
+        self.suffix.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn convention(&self) -> NameConvention {
+        self.convention
+    }
+
+    /// A single name with no family name, e.g. `Madonna`.
+    #[wasm_bindgen(getter)]
+    pub fn mononym(&self) -> bool {
+        self.family.is_empty() && self.given.len() == 1
+    }
+
+    /// Honorific and family name for formal address: `Dr. García López`, `Dr. 王`.
+    ///
+    /// Falls back to the full name when there is no honorific or no family name.
+    #[wasm_bindgen(getter)]
+    pub fn formal(&self) -> String {
+        match (&self.honorific, self.family.is_empty()) {
+            (Some(honorific), false) => format!("{} {}", honorific, self.family_name()),
+            (Some(honorific), true) => format!("{} {}", honorific, self.given_name()),
+            (None, _) => self.full(),
+        }
+    }
+
+    /// The name used for informal address: the first given name, or every given name for
+    /// Hispanic and family-first names, where compound given names are common.
+    #[wasm_bindgen(getter)]
+    pub fn informal(&self) -> String {
+        match self.convention {
+            NameConvention::Hispanic | NameConvention::FamilyFirst => self.given_name(),
+            _ => self.given.first().cloned().unwrap_or_default(),
+        }
+    }
+
+    /// Given and family names in their written order, without honorific or suffix.
+    #[wasm_bindgen(getter)]
+    pub fn full(&self) -> String {
+        let separator = if self.is_unspaced() { "" } else { " " };
+        let (first, second) = if self.family_first {
+            (self.family_name(), self.given_name())
+        } else {
+            (self.given_name(), self.family_name())
+        };
+        [first, second]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json(&self) -> Result<JsValue, JsError> {
+        js::to_js(self)
+    }
+}
+
+impl ParsedName {
+    fn given_name(&self) -> String {
+        self.given.join(if self.is_unspaced() { "" } else { " " })
+    }
+
+    fn family_name(&self) -> String {
+        self.family.join(" ")
+    }
+
+    /// Han, Kana and Hangul names are written without spaces between family and given name.
+    fn is_unspaced(&self) -> bool {
+        self.family_first
+            && self
+                .family
+                .iter()
+                .chain(&self.given)
+                .all(|part| part.chars().all(|c| is_cjk(c) || is_hangul(c)))
+    }
+}
+
+fn is_han(c: char) -> bool {
+    matches!(c as u32, 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF | 0x20000..=0x2FA1F)
+}
+
+fn is_kana(c: char) -> bool {
+    matches!(c as u32, 0x3040..=0x30FF | 0x31F0..=0x31FF)
+}
+
+fn is_hangul(c: char) -> bool {
+    matches!(c as u32, 0xAC00..=0xD7AF | 0x1100..=0x11FF | 0x3130..=0x318F)
+}
+
+fn is_cjk(c: char) -> bool {
+    is_han(c) || is_kana(c)
+}
+
+/// The bare, lowercase form of a token used to match honorifics and suffixes.
+fn bare(token: &str) -> String {
+    token
+        .trim_matches(|c: char| c == '.' || c == ',')
+        .to_lowercase()
+}
+
+/// Whether `token` is one of `affixes` when `remaining` tokens would be left without it.
+fn is_affix(token: &str, affixes: &[&str], remaining: usize) -> bool {
+    let bare = bare(token);
+    if !affixes.contains(&bare.as_str()) {
+        return false;
+    }
+    !AMBIGUOUS_AFFIXES.contains(&bare.as_str())
+        || token.trim_end_matches(',').ends_with('.')
+        || remaining >= 2
+}
+
+/// Title-cases a name written entirely in one case, keeping particles lowercase.
+fn fix_case(tokens: &mut [String]) {
+    let letters = || {
+        tokens
+            .iter()
+            .flat_map(|token| token.chars())
+            .filter(|c| c.is_alphabetic())
+    };
+    let single_case = letters().all(char::is_lowercase) || letters().all(char::is_uppercase);
+    if !single_case || letters().all(|c| !c.is_lowercase() && !c.is_uppercase()) {
+        return;
+    }
+    for (index, token) in tokens.iter_mut().enumerate() {
+        let lower = token.to_lowercase();
+        if index > 0 && PARTICLES.contains(&lower.as_str()) {
+            *token = lower;
+            continue;
+        }
+        let mut capitalize = true;
+        *token = lower
+            .chars()
+            .flat_map(|c| {
+                let out: Vec<char> = if capitalize {
+                    c.to_uppercase().collect()
+                } else {
+                    vec![c]
+                };
+                capitalize = matches!(c, '-' | '\'' | '’');
+                out
+            })
+            .collect();
+    }
+}
+
+/// Splits off the family name units from the end of `tokens`: a word and the particles
+/// before it, joined to the previous unit by `y`.
+fn take_family_units(tokens: &mut Vec<String>, units: usize) -> Vec<String> {
+    let mut start = tokens.len();
+    for _ in 0..units {
+        if start <= 1 {
+            break;
+        }
+        start -= 1;
+        while start > 1 && PARTICLES.contains(&tokens[start - 1].to_lowercase().as_str()) {
+            start -= 1;
+        }
+    }
+    tokens.split_off(start)
+}
+
+/// Splits an unspaced Han or Hangul name into its family and given names.
+///
+/// Only names written wholly in Han or wholly in Hangul are split. Anything else, such as a
+/// Japanese name with kana or `々`, has no reliable family-name length and is left whole.
+fn split_unspaced(name: &str) -> Option<(String, String)> {
+    let compounds = if name.chars().all(is_hangul) {
+        KOREAN_COMPOUND_FAMILY_NAMES
+    } else if name.chars().all(is_han) {
+        CHINESE_COMPOUND_FAMILY_NAMES
+    } else {
+        return None;
+    };
+    if name.chars().count() < 2 {
+        return None;
+    }
+    let family_len = compounds
+        .iter()
+        .find(|compound| name.starts_with(*compound) && name.chars().count() > 2)
+        .map_or_else(
+            || name.chars().next().map_or(0, char::len_utf8),
+            |compound| compound.len(),
+        );
+    let (family, given) = name.split_at(family_len);
+    Some((family.to_string(), given.to_string()))
+}
+
+/// Splits a full name into honorific, given names, family names and suffix.
+///
+/// The name is normalized first. `Family, Given` input is honored, and names written in a
+/// single case are title-cased.
+pub fn parse(name: &str, convention: NameConvention) -> ParsedName {
+    let normalized = normalize(name);
+    let mut tokens: Vec<String> = normalized.as_str().split(' ').map(String::from).collect();
+    tokens.retain(|token| !token.is_empty());
+
+    let mut parsed = ParsedName::default();
+
+    let mut honorifics = Vec::new();
+    while tokens.len() > 1 && is_affix(&tokens[0], HONORIFICS, tokens.len() - 1) {
+        honorifics.push(tokens.remove(0));
+    }
+    parsed.honorific = Some(honorifics.join(" ")).filter(|h| !h.is_empty());
+
+    let mut suffixes = Vec::new();
+    while tokens.len() > 1 && is_affix(&tokens[tokens.len() - 1], SUFFIXES, tokens.len() - 1) {
+        suffixes.insert(
+            0,
+            tokens
+                .pop()
+                .unwrap_or_default()
+                .trim_end_matches(',')
+                .to_string(),
+        );
+    }
+    parsed.suffix = Some(suffixes.join(" ")).filter(|s| !s.is_empty());
+    if let Some(last) = tokens.last_mut() {
+        *last = last.trim_end_matches(',').to_string();
+    }
+
+    // `García López, María` puts the family name first explicitly.
+    if let Some(comma) = tokens.iter().position(|token| token.ends_with(',')) {
+        let mut given = tokens.split_off(comma + 1);
+        let mut family = tokens;
+        if let Some(last) = family.last_mut() {
+            *last = last.trim_end_matches(',').to_string();
+        }
+        fix_case(&mut family);
+        fix_case(&mut given);
+        parsed.convention = match convention {
+            NameConvention::Auto => NameConvention::Western,
+            other => other,
+        };
+        parsed.family = family;
+        parsed.given = given;
+        return parsed;
+    }
+
+    let east_asian = tokens
+        .iter()
+        .flat_map(|token| token.chars())
+        .any(|c| is_cjk(c) || is_hangul(c));
+    parsed.convention = match convention {
+        NameConvention::Auto if east_asian => NameConvention::FamilyFirst,
+        NameConvention::Auto => NameConvention::Western,
+        other => other,
+    };
+    fix_case(&mut tokens);
+
+    match (parsed.convention, tokens.len()) {
+        (_, 0) => {}
+        (NameConvention::FamilyFirst, 1) => {
+            parsed.family_first = true;
+            match split_unspaced(&tokens[0]) {
+                Some((family, given)) => {
+                    parsed.family = vec![family];
+                    parsed.given = vec![given];
+                }
+                None => parsed.given = tokens,
+            }
+        }
+        (_, 1) => parsed.given = tokens,
+        (NameConvention::FamilyFirst, _) => {
+            parsed.family_first = true;
+            parsed.family = vec![tokens.remove(0)];
+            parsed.given = tokens;
+        }
+        (NameConvention::Hispanic, _) => {
+            let units = if tokens.len() > 2 { 2 } else { 1 };
+            parsed.family = take_family_units(&mut tokens, units);
+            parsed.given = tokens;
+        }
+        _ => {
+            parsed.family = take_family_units(&mut tokens, 1);
+            parsed.given = tokens;
+        }
+    }
+    parsed
+}
+
+/// Register of a greeting.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GreetingStyle {
+    /// `Hello, María!`
+    #[default]
+    Informal,
+    /// `Dear Dr. García López`
+    Formal,
+}
+
+/// Splits a full name into honorific, given names, family names and suffix.
+#[wasm_bindgen]
+pub fn parse_name(name: &str, convention: NameConvention) -> ParsedName {
+    parse(name, convention)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts(name: &str) -> (Option<String>, Vec<String>, Vec<String>, Option<String>) {
+        let parsed = parse(name, NameConvention::Auto);
+        (parsed.honorific, parsed.given, parsed.family, parsed.suffix)
+    }
+
+    fn strings(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn ambiguous_affixes_need_a_period_or_a_full_name() {
+        assert_eq!(
+            parts("M Smith"),
+            (None, strings(&["M"]), strings(&["Smith"]), None)
+        );
+        assert_eq!(
+            parts("M. Smith"),
+            (Some("M.".to_string()), strings(&["Smith"]), vec![], None)
+        );
+        assert_eq!(
+            parts("M Jean Dupont"),
+            (
+                Some("M".to_string()),
+                strings(&["Jean"]),
+                strings(&["Dupont"]),
+                None
+            )
+        );
+        assert_eq!(
+            parts("Ana V"),
+            (None, strings(&["Ana"]), strings(&["V"]), None)
+        );
+        assert_eq!(
+            parts("John Smith V"),
+            (
+                None,
+                strings(&["John"]),
+                strings(&["Smith"]),
+                Some("V".to_string())
+            )
+        );
+        assert_eq!(
+            parts("Sr García"),
+            (None, strings(&["Sr"]), strings(&["García"]), None)
+        );
+        assert_eq!(
+            parts("Sr. García"),
+            (Some("Sr.".to_string()), strings(&["García"]), vec![], None)
+        );
+    }
+
+    #[test]
+    fn unambiguous_affixes_are_always_taken() {
+        assert_eq!(
+            parts("Dr Smith"),
+            (Some("Dr".to_string()), strings(&["Smith"]), vec![], None)
+        );
+        assert_eq!(
+            parts("Martin Luther King, Jr."),
+            (
+                None,
+                strings(&["Martin", "Luther"]),
+                strings(&["King"]),
+                Some("Jr.".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn only_han_and_hangul_names_are_split_without_spaces() {
+        assert_eq!(
+            parts("王小明"),
+            (None, strings(&["小明"]), strings(&["王"]), None)
+        );
+        assert_eq!(
+            parts("欧阳娜娜"),
+            (None, strings(&["娜娜"]), strings(&["欧阳"]), None)
+        );
+        assert_eq!(
+            parts("남궁민"),
+            (None, strings(&["민"]), strings(&["남궁"]), None)
+        );
+        assert_eq!(parts("やまだはなこ").1, strings(&["やまだはなこ"]));
+        assert_eq!(parts("佐々木希").1, strings(&["佐々木希"]));
+        assert_eq!(
+            parts("山田 花子"),
+            (None, strings(&["花子"]), strings(&["山田"]), None)
+        );
+    }
+
+    #[test]
+    fn hispanic_names_carry_two_family_names() {
+        let parsed = parse("maría garcía lópez", NameConvention::Hispanic);
+        assert_eq!(parsed.given, strings(&["María"]));
+        assert_eq!(parsed.family, strings(&["García", "López"]));
+        assert_eq!(parsed.informal(), "María");
+        let parsed = parse("Ludwig van Beethoven", NameConvention::Auto);
+        assert_eq!(parsed.family, strings(&["van", "Beethoven"]));
+    }
+
+    #[test]
+    fn normalizes_and_reports_changes() {
+        let normalized = normalize("  Ana\u{200B}\u{202E}  Mari\u{301}a ");
+        assert_eq!(normalized.as_str(), "Ana María");
+        let kinds: Vec<_> = normalized
+            .changes
+            .iter()
+            .map(|change| change.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                NameChangeKind::BidiControlRemoved,
+                NameChangeKind::ZeroWidthRemoved,
+                NameChangeKind::Normalized,
+                NameChangeKind::Trimmed,
+                NameChangeKind::WhitespaceCollapsed,
+            ]
+        );
+        assert_eq!(normalize("Аnna").confusable_words, strings(&["Аnna"]));
+    }
+}
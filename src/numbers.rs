@@ -0,0 +1,139 @@
+use crate::locale::LanguageTag;
+use crate::plural::{self, PluralCategory};
+
+/// Zero digit of the default numbering system for a locale.
+fn zero_digit(tag: &LanguageTag) -> char {
+    match (tag.language.as_str(), tag.region.as_deref()) {
+        // The Maghreb uses Western digits for Arabic.
+        ("ar", Some("DZ" | "EH" | "LY" | "MA" | "TN")) => '0',
+        ("ar", _) => '\u{0660}',
+        ("fa", _) => '\u{06F0}',
+        ("bn", _) => '\u{09E6}',
+        _ => '0',
+    }
+}
+
+/// Formats an integer with the digits `locale` uses by default.
+pub fn format_integer(n: i64, locale: &str) -> String {
+    let digits = n.to_string();
+    let zero = match LanguageTag::parse(locale) {
+        Some(tag) => zero_digit(&tag),
+        None => return digits,
+    };
+    if zero == '0' {
+        return digits;
+    }
+    digits
+        .chars()
+        .map(|c| match c.to_digit(10) {
+            Some(d) => char::from_u32(zero as u32 + d).unwrap_or(c),
+            None => c,
+        })
+        .collect()
+}
+
+/// English ordinal of `n`: `1st`, `2nd`, `3rd`, `11th`, `22nd`.
+pub fn english_ordinal(n: i64) -> String {
+    let suffix = match (n.unsigned_abs() % 10, n.unsigned_abs() % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
+
+/// Ordinal of `n` as written in `locale`: `18th`, `18.`, `18e`, `18.º`, `18-й`, `第18`.
+///
+/// Languages without a digit-based ordinal fall back to the plain number.
+pub fn format_ordinal(n: i64, locale: &str) -> String {
+    let language = match LanguageTag::parse(locale) {
+        Some(tag) => tag.language,
+        None => return n.to_string(),
+    };
+    let digits = format_integer(n, locale);
+    let category = plural::ordinal(&language, n);
+    match language.as_str() {
+        "en" => english_ordinal(n),
+        "fr" if category == PluralCategory::One => format!("{}er", digits),
+        "fr" | "nl" => format!("{}e", digits),
+        "es" | "pt" | "gl" => format!("{}.º", digits),
+        "it" => format!("{}º", digits),
+        "ca" => {
+            let suffix = match n {
+                1 | 3 => "r",
+                2 => "n",
+                4 => "t",
+                _ => "è",
+            };
+            format!("{}{}", digits, suffix)
+        }
+        "sv" if category == PluralCategory::One => format!("{}:a", digits),
+        "sv" => format!("{}:e", digits),
+        "de" | "da" | "nb" | "nn" | "no" | "fi" | "et" | "is" | "cs" | "sk" | "pl" | "sl"
+        | "hr" | "sr" | "bs" | "hu" | "lv" | "tr" => format!("{}.", digits),
+        "ru" | "uk" | "be" => format!("{}-й", digits),
+        "ja" | "zh" => format!("第{}", digits),
+        "ko" => format!("{}번째", digits),
+        "id" | "ms" => format!("ke-{}", digits),
+        "vi" => format!("thứ {}", digits),
+        "fa" => format!("{}م", digits),
+        "hi" => {
+            let suffix = match category {
+                PluralCategory::One => "ला",
+                PluralCategory::Two => "रा",
+                PluralCategory::Few => "था",
+                PluralCategory::Many => "ठा",
+                _ => "वाँ",
+            };
+            format!("{}{}", digits, suffix)
+        }
+        "bn" => {
+            let suffix = match category {
+                PluralCategory::One => "ম",
+                PluralCategory::Two => "য়",
+                PluralCategory::Few => "র্থ",
+                PluralCategory::Many => "ষ্ঠ",
+                _ => "তম",
+            };
+            format!("{}{}", digits, suffix)
+        }
+        _ => digits,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_each_locales_digits() {
+        assert_eq!(format_integer(18, "ar"), "١٨");
+        assert_eq!(format_integer(18, "ar-MA"), "18");
+        assert_eq!(format_integer(-18, "fa"), "-۱۸");
+        assert_eq!(format_integer(18, "bn"), "১৮");
+        assert_eq!(format_integer(18, "not a locale"), "18");
+    }
+
+    #[test]
+    fn writes_ordinals_per_language() {
+        let ordinals: Vec<_> = [1, 2, 3, 4, 11, 12, 13, 21, 22, 111]
+            .iter()
+            .map(|&n| format_ordinal(n, "en"))
+            .collect();
+        assert_eq!(
+            ordinals,
+            ["1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st", "22nd", "111th"]
+        );
+        assert_eq!(format_ordinal(1, "fr"), "1er");
+        assert_eq!(format_ordinal(18, "fr-CA"), "18e");
+        assert_eq!(format_ordinal(18, "de"), "18.");
+        assert_eq!(format_ordinal(18, "es"), "18.º");
+        assert_eq!(format_ordinal(21, "sv"), "21:a");
+        assert_eq!(format_ordinal(18, "sv"), "18:e");
+        assert_eq!(format_ordinal(18, "ja"), "第18");
+        assert_eq!(format_ordinal(18, "fa"), "۱۸م");
+        assert_eq!(format_ordinal(18, "ar"), "١٨");
+    }
+}
@@ -0,0 +1,308 @@
+use serde::Serialize;
+
+use crate::locale::LanguageTag;
+
+/// CLDR plural categories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    /// Parses a CLDR category name such as `few`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "zero" => Some(PluralCategory::Zero),
+            "one" => Some(PluralCategory::One),
+            "two" => Some(PluralCategory::Two),
+            "few" => Some(PluralCategory::Few),
+            "many" => Some(PluralCategory::Many),
+            "other" => Some(PluralCategory::Other),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PluralCategory::Zero => "zero",
+            PluralCategory::One => "one",
+            PluralCategory::Two => "two",
+            PluralCategory::Few => "few",
+            PluralCategory::Many => "many",
+            PluralCategory::Other => "other",
+        }
+    }
+}
+
+/// CLDR cardinal plural category of the integer `n` in `language` (a bare language subtag).
+pub fn cardinal(language: &str, n: i64) -> PluralCategory {
+    use PluralCategory::*;
+
+    let n = n.unsigned_abs();
+    let (n10, n100) = (n % 10, n % 100);
+    let millions = n != 0 && n.is_multiple_of(1_000_000);
+    match language {
+        "ja" | "ko" | "zh" | "th" | "vi" | "id" | "ms" | "my" | "lo" | "km" | "yo" => Other,
+        "fr" => match n {
+            0 | 1 => One,
+            _ if millions => Many,
+            _ => Other,
+        },
+        "pt" => match n {
+            0 | 1 => One,
+            _ if millions => Many,
+            _ => Other,
+        },
+        "es" | "it" | "ca" => match n {
+            1 => One,
+            _ if millions => Many,
+            _ => Other,
+        },
+        "hi" | "bn" | "fa" | "gu" | "kn" | "mr" | "am" | "zu" => match n {
+            0 | 1 => One,
+            _ => Other,
+        },
+        "ru" | "uk" | "be" => match (n10, n100) {
+            (1, _) if n100 != 11 => One,
+            (2..=4, _) if !(12..=14).contains(&n100) => Few,
+            _ => Many,
+        },
+        "hr" | "sr" | "bs" => match (n10, n100) {
+            (1, _) if n100 != 11 => One,
+            (2..=4, _) if !(12..=14).contains(&n100) => Few,
+            _ => Other,
+        },
+        "pl" => match (n, n10) {
+            (1, _) => One,
+            (_, 2..=4) if !(12..=14).contains(&n100) => Few,
+            _ => Many,
+        },
+        "cs" | "sk" => match n {
+            1 => One,
+            2..=4 => Few,
+            _ => Other,
+        },
+        "lt" => match (n10, n100) {
+            (_, 11..=19) => Other,
+            (1, _) => One,
+            (2..=9, _) => Few,
+            _ => Other,
+        },
+        "lv" => match (n10, n100) {
+            (0, _) | (_, 11..=19) => Zero,
+            (1, _) => One,
+            _ => Other,
+        },
+        "sl" => match n100 {
+            1 => One,
+            2 => Two,
+            3 | 4 => Few,
+            _ => Other,
+        },
+        "ar" => match (n, n100) {
+            (0, _) => Zero,
+            (1, _) => One,
+            (2, _) => Two,
+            (_, 3..=10) => Few,
+            (_, 11..=99) => Many,
+            _ => Other,
+        },
+        "he" => match n {
+            1 => One,
+            2 => Two,
+            _ => Other,
+        },
+        "ga" => match n {
+            1 => One,
+            2 => Two,
+            3..=6 => Few,
+            7..=10 => Many,
+            _ => Other,
+        },
+        "cy" => match n {
+            0 => Zero,
+            1 => One,
+            2 => Two,
+            3 => Few,
+            6 => Many,
+            _ => Other,
+        },
+        "ro" => match n {
+            1 => One,
+            0 => Few,
+            _ if (1..=19).contains(&n100) => Few,
+            _ => Other,
+        },
+        _ => match n {
+            1 => One,
+            _ => Other,
+        },
+    }
+}
+
+/// CLDR ordinal plural category of the integer `n` in `language`, e.g. `two` for English 22.
+pub fn ordinal(language: &str, n: i64) -> PluralCategory {
+    use PluralCategory::*;
+
+    let n = n.unsigned_abs();
+    let (n10, n100) = (n % 10, n % 100);
+    match language {
+        "en" => match (n10, n100) {
+            (1, _) if n100 != 11 => One,
+            (2, _) if n100 != 12 => Two,
+            (3, _) if n100 != 13 => Few,
+            _ => Other,
+        },
+        "fr" | "ms" | "vi" | "fil" | "ro" | "ga" => match n {
+            1 => One,
+            _ => Other,
+        },
+        "it" => match n {
+            8 | 11 | 80 | 800 => Many,
+            _ => Other,
+        },
+        "sv" => match (n10, n100) {
+            (1 | 2, _) if n100 != 11 && n100 != 12 => One,
+            _ => Other,
+        },
+        "ca" => match n {
+            1 | 3 => One,
+            2 => Two,
+            4 => Few,
+            _ => Other,
+        },
+        "hi" | "gu" => match n {
+            1 => One,
+            2 | 3 => Two,
+            4 => Few,
+            6 => Many,
+            _ => Other,
+        },
+        "bn" => match n {
+            1 | 5 | 7..=10 => One,
+            2 | 3 => Two,
+            4 => Few,
+            6 => Many,
+            _ => Other,
+        },
+        "cy" => match n {
+            0 | 7..=9 => Zero,
+            1 => One,
+            2 => Two,
+            3 | 4 => Few,
+            5 | 6 => Many,
+            _ => Other,
+        },
+        "hu" => match n {
+            1 | 5 => One,
+            _ => Other,
+        },
+        "ne" => match n {
+            1..=4 => One,
+            _ => Other,
+        },
+        "sq" => match (n, n10) {
+            (1, _) => One,
+            (_, 4) if n100 != 14 => Many,
+            _ => Other,
+        },
+        _ => Other,
+    }
+}
+
+/// Cardinal category of `n` in `locale`, honouring regional differences such as `pt-PT`.
+pub fn category(n: i64, locale: &str) -> PluralCategory {
+    match LanguageTag::parse(locale) {
+        Some(tag) if tag.language == "pt" && tag.region.as_deref() == Some("PT") => {
+            match n.unsigned_abs() {
+                1 => PluralCategory::One,
+                n if n != 0 && n.is_multiple_of(1_000_000) => PluralCategory::Many,
+                _ => PluralCategory::Other,
+            }
+        }
+        Some(tag) => cardinal(&tag.language, n),
+        None => cardinal("", n),
+    }
+}
+
+/// Ordinal category of `n` in `locale`.
+pub fn ordinal_category(n: i64, locale: &str) -> PluralCategory {
+    match LanguageTag::parse(locale) {
+        Some(tag) => ordinal(&tag.language, n),
+        None => PluralCategory::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PluralCategory::*;
+    use super::*;
+
+    fn assert_samples(locale: &str, category: PluralCategory, samples: &[i64]) {
+        for &n in samples {
+            assert_eq!(cardinal(locale, n), category, "{} in {}", n, locale);
+        }
+    }
+
+    #[test]
+    fn arabic_uses_all_six_categories() {
+        assert_samples("ar", Zero, &[0]);
+        assert_samples("ar", One, &[1]);
+        assert_samples("ar", Two, &[2]);
+        assert_samples("ar", Few, &[3, 7, 10, 103, 110, 1003]);
+        assert_samples("ar", Many, &[11, 26, 99, 111, 1011]);
+        assert_samples("ar", Other, &[100, 101, 102, 200, 202, 1000]);
+    }
+
+    #[test]
+    fn russian_follows_the_last_two_digits() {
+        assert_samples("ru", One, &[1, 21, 31, 101, 1001]);
+        assert_samples("ru", Few, &[2, 3, 4, 22, 24, 102]);
+        assert_samples("ru", Many, &[0, 5, 11, 12, 14, 19, 100, 111, 1000]);
+    }
+
+    #[test]
+    fn polish_treats_only_one_itself_as_one() {
+        assert_samples("pl", One, &[1]);
+        assert_samples("pl", Few, &[2, 4, 22, 24, 32, 102]);
+        assert_samples("pl", Many, &[0, 5, 11, 12, 14, 21, 25, 100, 112, 1000]);
+    }
+
+    #[test]
+    fn romanian_counts_teens_past_each_hundred_as_few() {
+        assert_samples("ro", One, &[1]);
+        assert_samples("ro", Few, &[0, 2, 19, 101, 119, 1001]);
+        assert_samples("ro", Other, &[20, 100, 120, 1000]);
+    }
+
+    #[test]
+    fn welsh_cardinals_and_ordinals() {
+        assert_samples("cy", Zero, &[0]);
+        assert_samples("cy", One, &[1]);
+        assert_samples("cy", Two, &[2]);
+        assert_samples("cy", Few, &[3]);
+        assert_samples("cy", Many, &[6]);
+        assert_samples("cy", Other, &[4, 5, 7, 10, 20, 100]);
+        let ordinals: Vec<_> = (0..=10).map(|n| ordinal("cy", n)).collect();
+        assert_eq!(
+            ordinals,
+            [Zero, One, Two, Few, Few, Many, Many, Zero, Zero, Zero, Other]
+        );
+    }
+
+    #[test]
+    fn locales_pick_regional_rules() {
+        assert_eq!(category(0, "pt-BR"), One);
+        assert_eq!(category(0, "pt-PT"), Other);
+        assert_eq!(category(-21, "ru-RU"), One);
+        assert_eq!(category(1, "not a locale"), One);
+        assert_eq!(ordinal_category(22, "en-GB"), Two);
+        assert_eq!(ordinal_category(12, "en"), Other);
+    }
+}
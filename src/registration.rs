@@ -0,0 +1,166 @@
+use chrono::{Months, NaiveDate};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::dates;
+use crate::error::EligibilityError;
+use crate::rules::VotingAgeRule;
+
+/// What a person may do towards voting, from nothing to casting a ballot.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum RegistrationStatus {
+    /// Too young to register or pre-register.
+    Ineligible,
+    /// Can pre-register; the registration becomes active at the voting age.
+    PreRegistrationEligible,
+    /// Can register in full ahead of reaching the voting age.
+    RegistrationEligible,
+    /// Has reached the voting age.
+    VotingEligible,
+}
+
+impl RegistrationStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RegistrationStatus::Ineligible => "Ineligible",
+            RegistrationStatus::PreRegistrationEligible => "PreRegistrationEligible",
+            RegistrationStatus::RegistrationEligible => "RegistrationEligible",
+            RegistrationStatus::VotingEligible => "VotingEligible",
+        }
+    }
+
+    /// Catalog id of the message describing this status.
+    pub fn message_id(self) -> &'static str {
+        match self {
+            RegistrationStatus::Ineligible => "registration-ineligible",
+            RegistrationStatus::PreRegistrationEligible => "registration-preregister",
+            RegistrationStatus::RegistrationEligible => "registration-register",
+            RegistrationStatus::VotingEligible => "registration-vote",
+        }
+    }
+}
+
+/// The day full registration opens for someone born on `dob` under `rule`.
+pub fn registration_opens(
+    dob: NaiveDate,
+    rule: &VotingAgeRule,
+) -> Result<NaiveDate, EligibilityError> {
+    let years = rule.voting_age().into();
+    dates::anniversary(dob, years, rule.leap_day())?
+        .checked_sub_months(Months::new(rule.registration_lead_months().into()))
+        .ok_or(EligibilityError::DateOutOfRange { date: dob, years })
+}
+
+/// Registration status on `on` of someone born on `dob`.
+pub fn status_on(
+    dob: NaiveDate,
+    on: NaiveDate,
+    rule: &VotingAgeRule,
+) -> Result<RegistrationStatus, EligibilityError> {
+    let birthday = |years: u8| dates::anniversary(dob, years.into(), rule.leap_day());
+    if on >= birthday(rule.voting_age())? {
+        return Ok(RegistrationStatus::VotingEligible);
+    }
+    if rule.registration_lead_months() > 0 && on >= registration_opens(dob, rule)? {
+        return Ok(RegistrationStatus::RegistrationEligible);
+    }
+    Ok(match rule.preregistration_age() {
+        Some(age) if on >= birthday(age)? => RegistrationStatus::PreRegistrationEligible,
+        _ => RegistrationStatus::Ineligible,
+    })
+}
+
+/// Registration status of someone known only to be `age`.
+///
+/// A registration window shorter than a year cannot be placed without a date of birth, so
+/// it only counts once the whole window fits inside the year before the voting age.
+pub fn status_for_age(age: i32, rule: &VotingAgeRule) -> RegistrationStatus {
+    let voting_age = i32::from(rule.voting_age());
+    let registration_age = voting_age - i32::from(rule.registration_lead_months()) / 12;
+    if age >= voting_age {
+        RegistrationStatus::VotingEligible
+    } else if age >= registration_age && registration_age < voting_age {
+        RegistrationStatus::RegistrationEligible
+    } else if rule
+        .preregistration_age()
+        .is_some_and(|preregistration| age >= i32::from(preregistration))
+    {
+        RegistrationStatus::PreRegistrationEligible
+    } else {
+        RegistrationStatus::Ineligible
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules;
+
+    fn date(text: &str) -> NaiveDate {
+        dates::parse_iso_date(text).unwrap()
+    }
+
+    fn status(code: &str, dob: &str, on: &str) -> RegistrationStatus {
+        status_on(date(dob), date(on), &rules::lookup(code).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn moves_through_each_status_with_age() {
+        let statuses: Vec<_> = ["2026-05-31", "2026-06-01", "2028-06-01"]
+            .iter()
+            .map(|on| status("GB", "2010-06-01", on))
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                RegistrationStatus::Ineligible,
+                RegistrationStatus::PreRegistrationEligible,
+                RegistrationStatus::VotingEligible
+            ]
+        );
+    }
+
+    #[test]
+    fn opens_registration_ahead_of_the_birthday() {
+        let texas = rules::lookup("US-TX").unwrap();
+        assert_eq!(
+            registration_opens(date("2008-12-31"), &texas),
+            Ok(date("2026-10-31"))
+        );
+        assert_eq!(
+            status("US-TX", "2008-12-31", "2026-10-30"),
+            RegistrationStatus::Ineligible
+        );
+        assert_eq!(
+            status("US-TX", "2008-12-31", "2026-10-31"),
+            RegistrationStatus::RegistrationEligible
+        );
+    }
+
+    #[test]
+    fn places_only_whole_year_windows_without_a_date_of_birth() {
+        let by_age = |code: &str, age| status_for_age(age, &rules::lookup(code).unwrap());
+        assert_eq!(
+            by_age("US-NJ", 17),
+            RegistrationStatus::RegistrationEligible
+        );
+        assert_eq!(by_age("US-TX", 17), RegistrationStatus::Ineligible);
+        assert_eq!(
+            by_age("GB", 16),
+            RegistrationStatus::PreRegistrationEligible
+        );
+        assert_eq!(by_age("GB", 15), RegistrationStatus::Ineligible);
+        assert_eq!(by_age("GB", 18), RegistrationStatus::VotingEligible);
+    }
+
+    #[test]
+    fn orders_statuses_from_nothing_to_voting() {
+        assert!(RegistrationStatus::Ineligible < RegistrationStatus::PreRegistrationEligible);
+        assert!(RegistrationStatus::RegistrationEligible < RegistrationStatus::VotingEligible);
+        assert_eq!(
+            RegistrationStatus::RegistrationEligible.message_id(),
+            "registration-register"
+        );
+    }
+}
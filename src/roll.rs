@@ -0,0 +1,698 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use csv::{Position, ReaderBuilder, StringRecord};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::dates;
+use crate::eligibility::{self, EligibilityResult, EligibilityStatus};
+use crate::error::EligibilityError;
+use crate::js;
+use crate::rules::{self, VotingAgeRule};
+use crate::trace::DecisionTrace;
+use crate::validation::{self, ErrorMode, RowError, ValidationErrorKind, MAX_AGE};
+
+/// Byte-order mark that a chunked reader can leave on the first header.
+const BOM: char = '\u{feff}';
+
+/// Delimiters `RollOptions::delimiter_for` looks for, most likely first.
+const DELIMITERS: [u8; 4] = [b',', b';', b'\t', b'|'];
+
+/// Options accepted by `check_roll_csv`. Every field is optional.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RollOptions {
+    pub name_column: String,
+    pub age_column: String,
+    pub dob_column: String,
+    /// Jurisdiction applied to every row; the default voting age is used when unset.
+    pub jurisdiction: Option<String>,
+    /// Per-row jurisdiction, overriding `jurisdiction` when the cell is non-empty.
+    pub jurisdiction_column: Option<String>,
+    /// Election date that DOB columns are evaluated against.
+    pub election_date: Option<String>,
+    /// Field delimiter; detected from the header line when unset, or `,` for streams.
+    pub delimiter: Option<char>,
+    pub error_mode: ErrorMode,
+    /// Attach a decision trace to every row result.
+    pub trace: bool,
+}
+
+impl Default for RollOptions {
+    fn default() -> Self {
+        RollOptions {
+            name_column: "name".to_string(),
+            age_column: "age".to_string(),
+            dob_column: "dob".to_string(),
+            jurisdiction: None,
+            jurisdiction_column: None,
+            election_date: None,
+            delimiter: None,
+            error_mode: ErrorMode::default(),
+            trace: false,
+        }
+    }
+}
+
+impl RollOptions {
+    pub(crate) fn delimiter_byte(&self) -> Result<u8, EligibilityError> {
+        let delimiter = self.delimiter.unwrap_or(',');
+        u8::try_from(delimiter)
+            .ok()
+            .filter(u8::is_ascii)
+            .ok_or(EligibilityError::InvalidDelimiter(delimiter))
+    }
+
+    /// The delimiter for `input`: the one in the options, or else the likeliest candidate
+    /// outside quotes on the header line.
+    pub(crate) fn delimiter_for(&self, input: &str) -> Result<u8, EligibilityError> {
+        if self.delimiter.is_some() {
+            return self.delimiter_byte();
+        }
+        let mut counts = [0usize; DELIMITERS.len()];
+        let mut quoted = false;
+        for byte in input.bytes() {
+            if byte == b'\n' && !quoted {
+                break;
+            } else if byte == b'"' {
+                quoted = !quoted;
+            } else if let Some(index) = DELIMITERS.iter().position(|&candidate| candidate == byte) {
+                counts[index] += usize::from(!quoted);
+            }
+        }
+        // Ties go to the earlier candidate, so a header with no delimiter at all reads as `,`.
+        let index = counts
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|&(_, count)| count)
+            .map_or(0, |(index, _)| index);
+        Ok(DELIMITERS[index])
+    }
+}
+
+/// Outcome for a single valid voter-roll row.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RowResult {
+    /// Line of the input the row starts on; the header is line 1.
+    pub row: u64,
+    pub name: Option<String>,
+    pub result: EligibilityResult,
+    /// How the result was reached, when the options ask for traces.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace: Option<DecisionTrace>,
+}
+
+/// Row counts across a voter roll.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollSummary {
+    pub total: usize,
+    pub eligible: usize,
+    pub just_eligible: usize,
+    pub not_yet_eligible: usize,
+    pub invalid: usize,
+}
+
+impl RollSummary {
+    pub fn record(&mut self, result: &EligibilityResult) {
+        self.total += 1;
+        match result.status() {
+            EligibilityStatus::Eligible => self.eligible += 1,
+            EligibilityStatus::JustEligible => self.just_eligible += 1,
+            EligibilityStatus::NotYetEligible => self.not_yet_eligible += 1,
+        }
+    }
+
+    pub fn record_invalid(&mut self) {
+        self.total += 1;
+        self.invalid += 1;
+    }
+
+    pub fn merge(&mut self, other: &RollSummary) {
+        self.total += other.total;
+        self.eligible += other.eligible;
+        self.just_eligible += other.just_eligible;
+        self.not_yet_eligible += other.not_yet_eligible;
+        self.invalid += other.invalid;
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RollReport {
+    pub rows: Vec<RowResult>,
+    pub errors: Vec<RowError>,
+    pub summary: RollSummary,
+}
+
+impl RollReport {
+    /// Adds a checked row, stopping with an error in fail-fast mode.
+    pub fn push(
+        &mut self,
+        outcome: Result<RowResult, RowError>,
+        mode: ErrorMode,
+    ) -> Result<(), EligibilityError> {
+        match outcome {
+            Ok(row) => {
+                self.summary.record(&row.result);
+                self.rows.push(row);
+            }
+            Err(error) => {
+                self.summary.record_invalid();
+                self.errors.push(mode.handle(error)?);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Column {
+    index: usize,
+    name: String,
+}
+
+/// Checks voter-roll records against the columns named in `RollOptions`.
+pub(crate) struct RowChecker {
+    name: Option<Column>,
+    age: Option<Column>,
+    dob: Option<Column>,
+    jurisdiction: Option<Column>,
+    election: Option<NaiveDate>,
+    default_rule: Option<VotingAgeRule>,
+    rules: HashMap<String, VotingAgeRule>,
+    trace: bool,
+}
+
+impl RowChecker {
+    pub fn new(headers: &StringRecord, options: &RollOptions) -> Result<Self, EligibilityError> {
+        let column = |name: &str| {
+            headers
+                .iter()
+                .position(|header| header.trim_start_matches(BOM).trim() == name)
+                .map(|index| Column {
+                    index,
+                    name: name.to_string(),
+                })
+        };
+        let age = column(&options.age_column);
+        let dob = column(&options.dob_column);
+        let jurisdiction = match &options.jurisdiction_column {
+            Some(name) => {
+                Some(column(name).ok_or_else(|| EligibilityError::MissingColumn(name.clone()))?)
+            }
+            None => None,
+        };
+        let election = match &options.election_date {
+            Some(date) => Some(dates::parse_iso_date(date)?),
+            None => None,
+        };
+        let default_rule = match &options.jurisdiction {
+            Some(code) => Some(rule_as_of(code, election)?),
+            None => None,
+        };
+        // Without an election date a DOB column cannot stand in for the age column.
+        if age.is_none() && (dob.is_none() || election.is_none()) {
+            return Err(EligibilityError::MissingColumn(options.age_column.clone()));
+        }
+
+        Ok(RowChecker {
+            name: column(&options.name_column),
+            age,
+            dob,
+            jurisdiction,
+            election,
+            default_rule,
+            rules: HashMap::new(),
+            trace: options.trace,
+        })
+    }
+
+    pub fn check(&mut self, row: u64, record: &StringRecord) -> Result<RowResult, RowError> {
+        let name = self
+            .name
+            .as_ref()
+            .and_then(|column| record.get(column.index))
+            .map(|name| name.trim().to_string());
+        let result = self.evaluate(row, record)?;
+        let trace = self.trace.then(|| result.trace());
+        Ok(RowResult {
+            row,
+            name,
+            result,
+            trace,
+        })
+    }
+
+    fn evaluate(&mut self, row: u64, record: &StringRecord) -> Result<EligibilityResult, RowError> {
+        let error =
+            |column: &Column, value: &str, kind| RowError::new(row, &column.name, value, kind);
+        let cell = |column: &Column| {
+            record
+                .get(column.index)
+                .map(str::trim)
+                .ok_or_else(|| error(column, "", ValidationErrorKind::MissingColumn))
+        };
+
+        let rule = match self.jurisdiction.clone() {
+            Some(column) => {
+                let code = cell(&column)?;
+                self.rule_for(Some(code).filter(|code| !code.is_empty()))
+                    .map_err(|failure| error(&column, code, ValidationErrorKind::of(&failure)))?
+            }
+            None => self
+                .rule_for(None)
+                .expect("the default rule is always known"),
+        };
+
+        if let (Some(column), Some(election)) = (&self.dob, self.election) {
+            let dob = cell(column)?;
+            if !dob.is_empty() || self.age.is_none() {
+                let date = dates::parse_iso_date(dob).map_err(|_| {
+                    let kind = if dob.is_empty() {
+                        ValidationErrorKind::MissingValue
+                    } else {
+                        ValidationErrorKind::InvalidDate
+                    };
+                    error(column, dob, kind)
+                })?;
+                return eligibility::evaluate_dob(date, election, &rule)
+                    .map_err(|failure| error(column, dob, ValidationErrorKind::of(&failure)));
+            }
+        }
+
+        let Some(column) = &self.age else {
+            unreachable!("RowChecker::new requires an age column when DOB cannot be used");
+        };
+        let age = cell(column)?;
+        if age.is_empty() {
+            return Err(error(column, age, ValidationErrorKind::MissingValue));
+        }
+        let parsed = age
+            .parse::<i32>()
+            .map_err(|_| error(column, age, ValidationErrorKind::NotANumber))?;
+        if !(0..=MAX_AGE).contains(&parsed) {
+            return Err(error(column, age, ValidationErrorKind::AgeOutOfRange));
+        }
+        eligibility::evaluate_age(parsed, &rule)
+            .map_err(|failure| error(column, age, ValidationErrorKind::of(&failure)))
+    }
+
+    fn rule_for(&mut self, jurisdiction: Option<&str>) -> Result<VotingAgeRule, EligibilityError> {
+        let Some(code) = jurisdiction else {
+            return Ok(self
+                .default_rule
+                .clone()
+                .unwrap_or_else(VotingAgeRule::default_rule));
+        };
+        if let Some(rule) = self.rules.get(code) {
+            return Ok(rule.clone());
+        }
+        let rule = rule_as_of(code, self.election)?;
+        self.rules.insert(code.to_string(), rule.clone());
+        Ok(rule)
+    }
+}
+
+/// The rule in force on the election date, or the current rule when there is none.
+fn rule_as_of(code: &str, election: Option<NaiveDate>) -> Result<VotingAgeRule, EligibilityError> {
+    match election {
+        Some(date) => rules::lookup_as_of(code, date),
+        None => rules::lookup(code),
+    }
+}
+
+/// Checks every row of a voter-roll CSV with a header row.
+pub fn check_roll(input: &str, options: &RollOptions) -> Result<RollReport, EligibilityError> {
+    let mut reader = ReaderBuilder::new()
+        .delimiter(options.delimiter_for(input)?)
+        .flexible(true)
+        .from_reader(input.as_bytes());
+    let mut checker = RowChecker::new(reader.headers()?, options)?;
+
+    let mut lines = Lines::new(input);
+    let mut report = RollReport::default();
+    for record in reader.records() {
+        let outcome = match record {
+            Ok(record) => checker.check(lines.at(record.position()), &record),
+            Err(error) => Err(lines.malformed(&error)),
+        };
+        report.push(outcome, options.error_mode)?;
+    }
+    Ok(report)
+}
+
+/// The byte a record at `position` really starts on, past the terminator and blank lines the
+/// reader counts as part of it.
+fn record_start(input: &[u8], position: Option<&Position>) -> usize {
+    let mut byte = position.map_or(0, |position| position.byte() as usize);
+    while matches!(input.get(byte), Some(b'\r' | b'\n')) {
+        byte += 1;
+    }
+    byte.min(input.len())
+}
+
+/// Line numbers of the records read from an input, counted from each record's first byte.
+///
+/// The csv reader starts a record that follows a CRLF terminator on the terminator's `\n`, so its
+/// own line numbers run short on CRLF input.
+struct Lines<'a> {
+    input: &'a [u8],
+    counted: usize,
+    line: u64,
+}
+
+impl<'a> Lines<'a> {
+    fn new(input: &'a str) -> Self {
+        Lines {
+            input: input.as_bytes(),
+            counted: 0,
+            line: 1,
+        }
+    }
+
+    /// The line `position` falls on; positions must come in input order.
+    fn at(&mut self, position: Option<&Position>) -> u64 {
+        let byte = record_start(self.input, position).max(self.counted);
+        let newlines = self.input[self.counted..byte]
+            .iter()
+            .filter(|&&byte| byte == b'\n')
+            .count();
+        self.line += newlines as u64;
+        self.counted = byte;
+        self.line
+    }
+
+    fn malformed(&mut self, error: &csv::Error) -> RowError {
+        RowError {
+            row: self.at(error.position()),
+            ..RowError::malformed(error)
+        }
+    }
+}
+
+/// Columns `annotate_roll` appends to every record.
+pub const ANNOTATION_COLUMNS: [&str; 4] = ["eligible", "status", "years_until_eligible", "rule_id"];
+
+/// Writes `input` back out with `ANNOTATION_COLUMNS` appended to each record.
+///
+/// Each record is copied byte for byte, up to and including its line terminator; only the
+/// appended fields are new. They use the input's delimiter and, when the header quotes every
+/// field, are quoted too, so a roll that quotes every field keeps doing so.
+pub fn annotate_roll(input: &str, options: &RollOptions) -> Result<Vec<u8>, EligibilityError> {
+    let delimiter = options.delimiter_for(input)?;
+    let quote_all = quotes_every_field(input.trim_start_matches(BOM), delimiter);
+    let appended = |fields: &mut Vec<u8>, values: &[&str]| {
+        for value in values {
+            fields.push(delimiter);
+            append_field(fields, value, delimiter, quote_all);
+        }
+    };
+
+    let mut reader = ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_reader(input.as_bytes());
+    let headers = reader.headers()?.clone();
+    let mut checker = RowChecker::new(&headers, options)?;
+
+    // Where each record starts, with the fields to append to it; the header starts at 0.
+    let mut header_fields = Vec::new();
+    appended(&mut header_fields, &ANNOTATION_COLUMNS);
+    let mut records = vec![(0, header_fields)];
+    let mut lines = Lines::new(input);
+    for record in reader.records() {
+        let (position, annotations) = match record {
+            Ok(record) => {
+                let annotations = match checker.check(lines.at(record.position()), &record) {
+                    Ok(row) => [
+                        row.result.eligible().to_string(),
+                        row.result.status().as_str().to_string(),
+                        row.result.years_until_eligible().to_string(),
+                        row.result.rule_id(),
+                    ],
+                    Err(error) => {
+                        options.error_mode.handle(error)?;
+                        Default::default()
+                    }
+                };
+                (record.position().cloned(), annotations)
+            }
+            Err(error) => {
+                options.error_mode.handle(lines.malformed(&error))?;
+                (error.position().cloned(), Default::default())
+            }
+        };
+        let mut fields = Vec::new();
+        appended(&mut fields, &annotations.each_ref().map(String::as_str));
+        let previous = records.last().map_or(0, |(start, _)| *start);
+        let start = record_start(input.as_bytes(), position.as_ref()).max(previous);
+        records.push((start, fields));
+    }
+
+    let mut output = Vec::with_capacity(input.len() + records.len() * 32);
+    let ends = records.iter().skip(1).map(|(start, _)| *start);
+    for ((start, fields), end) in records.iter().zip(ends.chain([input.len()])) {
+        let raw = &input[*start..end];
+        let content = raw.trim_end_matches(['\r', '\n']);
+        output.extend_from_slice(content.as_bytes());
+        output.extend_from_slice(fields);
+        output.extend_from_slice(&raw.as_bytes()[content.len()..]);
+    }
+    Ok(output)
+}
+
+/// Whether every field on the first line of `input` is quoted.
+fn quotes_every_field(input: &str, delimiter: u8) -> bool {
+    let mut quoted = false;
+    let mut field_start = true;
+    for byte in input.bytes() {
+        if field_start && byte != b'"' {
+            return false;
+        }
+        field_start = false;
+        match byte {
+            b'"' => quoted = !quoted,
+            b'\r' | b'\n' if !quoted => break,
+            _ if byte == delimiter && !quoted => field_start = true,
+            _ => {}
+        }
+    }
+    !field_start
+}
+
+/// Appends `value` as a CSV field, quoted when `quote` is set or the value needs it.
+fn append_field(output: &mut Vec<u8>, value: &str, delimiter: u8, quote: bool) {
+    let needs_quotes = value
+        .bytes()
+        .any(|byte| matches!(byte, b'"' | b'\r' | b'\n') || byte == delimiter);
+    if !(quote || needs_quotes) {
+        output.extend_from_slice(value.as_bytes());
+        return;
+    }
+    output.push(b'"');
+    output.extend_from_slice(value.replace('"', "\"\"").as_bytes());
+    output.push(b'"');
+}
+
+/// Checks a whole voter roll in one call, returning `{ rows, summary }`.
+#[wasm_bindgen]
+pub fn check_roll_csv(input: &str, options: JsValue) -> Result<JsValue, JsError> {
+    let options: RollOptions = js::options_from_js(options)?;
+    js::to_js(&check_roll(input, &options)?)
+}
+
+/// Returns the voter roll with eligibility columns appended, as text.
+#[wasm_bindgen]
+pub fn annotate_roll_csv(input: &str, options: JsValue) -> Result<String, JsError> {
+    let options: RollOptions = js::options_from_js(options)?;
+    Ok(String::from_utf8(annotate_roll(input, &options)?)?)
+}
+
+/// Returns the voter roll with eligibility columns appended, as a `Uint8Array` for `fs.writeFile`.
+#[wasm_bindgen]
+pub fn annotate_roll_csv_bytes(input: &str, options: JsValue) -> Result<Vec<u8>, JsError> {
+    let options: RollOptions = js::options_from_js(options)?;
+    Ok(annotate_roll(input, &options)?)
+}
+
+/// Validates a voter roll and returns its errors as a `row,column,value,kind,message` CSV.
+#[wasm_bindgen]
+pub fn validate_roll_csv(input: &str, options: JsValue) -> Result<String, JsError> {
+    let options: RollOptions = js::options_from_js(options)?;
+    let report = check_roll(input, &options)?;
+    Ok(validation::write_report(&report.errors)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(json: &str) -> RollOptions {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn checks_every_record_and_counts_statuses() {
+        let input = "name,age\nAda,17\nGrace,18\nAlan,40\n";
+        let report = check_roll(input, &RollOptions::default()).unwrap();
+        let statuses: Vec<_> = report
+            .rows
+            .iter()
+            .map(|row| (row.row, row.result.status()))
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                (2, EligibilityStatus::NotYetEligible),
+                (3, EligibilityStatus::JustEligible),
+                (4, EligibilityStatus::Eligible),
+            ]
+        );
+        assert_eq!(report.rows[0].name.as_deref(), Some("Ada"));
+        assert_eq!(report.summary.total, 3);
+        assert_eq!(report.summary.eligible, 1);
+        assert_eq!(report.summary.just_eligible, 1);
+        assert_eq!(report.summary.not_yet_eligible, 1);
+    }
+
+    #[test]
+    fn reads_quoted_fields_and_crlf_line_endings() {
+        let input =
+            "\u{feff}\"name\",\"age\"\r\n\"Lovelace, Ada\",\"36\"\r\n\"Line\r\nBreak\",17\r\n";
+        let report = check_roll(input, &RollOptions::default()).unwrap();
+        assert!(report.errors.is_empty());
+        assert_eq!(report.rows[0].name.as_deref(), Some("Lovelace, Ada"));
+        assert_eq!(report.rows[1].name.as_deref(), Some("Line\r\nBreak"));
+        assert_eq!(report.rows[1].row, 3);
+        assert_eq!(
+            report.rows[1].result.status(),
+            EligibilityStatus::NotYetEligible
+        );
+    }
+
+    #[test]
+    fn numbers_crlf_rows_from_their_first_line() {
+        let report = check_roll("age\r\n17\r\n\r\nabc\r\n", &RollOptions::default()).unwrap();
+        assert_eq!(report.rows[0].row, 2);
+        assert_eq!(
+            report.errors,
+            [RowError::new(
+                4,
+                "age",
+                "abc",
+                ValidationErrorKind::NotANumber
+            )]
+        );
+        assert_eq!(report.summary.invalid, 1);
+    }
+
+    #[test]
+    fn reports_jurisdictions_without_a_rule_on_the_election_date() {
+        let options = RollOptions {
+            jurisdiction_column: Some("jurisdiction".to_string()),
+            election_date: Some("2010-05-02".to_string()),
+            ..RollOptions::default()
+        };
+        crate::declarative::load(
+            r#"{"rules": [{"jurisdiction": "XB", "effectiveFrom": "2020-01-01", "votingAge": 16}]}"#,
+            crate::declarative::RuleFormat::Json,
+        )
+        .unwrap();
+        let report = check_roll("age,jurisdiction\n17,XB\n17,XX\n17,\n", &options).unwrap();
+        let kinds: Vec<_> = report.errors.iter().map(|error| error.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                ValidationErrorKind::NoRuleInForce,
+                ValidationErrorKind::UnknownJurisdiction
+            ]
+        );
+        assert_eq!(report.rows.len(), 1);
+    }
+
+    #[test]
+    fn evaluates_dates_of_birth_against_the_election() {
+        let options = options(r#"{"electionDate": "2026-11-03", "jurisdiction": "US"}"#);
+        let report = check_roll(
+            "name;dob\nAda;2008-11-03\n",
+            &RollOptions {
+                delimiter: Some(';'),
+                ..options
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            report.rows[0].result.status(),
+            EligibilityStatus::JustEligible
+        );
+    }
+
+    #[test]
+    fn reports_birthdays_past_the_calendar_as_out_of_range() {
+        let options = options(r#"{"electionDate": "+262142-12-31"}"#);
+        let report = check_roll("dob\n+262142-01-01\n2000-01-01\n", &options).unwrap();
+        assert_eq!(
+            report.errors,
+            [RowError::new(
+                2,
+                "dob",
+                "+262142-01-01",
+                ValidationErrorKind::DateOutOfRange
+            )]
+        );
+        assert_eq!(report.rows.len(), 1);
+    }
+
+    #[test]
+    fn needs_an_age_column_without_an_election_date() {
+        assert_eq!(
+            check_roll("name,dob\nAda,2008-11-03\n", &RollOptions::default()).unwrap_err(),
+            EligibilityError::MissingColumn("age".to_string())
+        );
+        assert_eq!(
+            check_roll("age\n1\n", &options(r#"{"delimiter": "é"}"#)).unwrap_err(),
+            EligibilityError::InvalidDelimiter('é')
+        );
+    }
+
+    fn annotate(input: &str) -> String {
+        String::from_utf8(annotate_roll(input, &RollOptions::default()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn copies_records_byte_for_byte() {
+        assert_eq!(
+            annotate("\"name\",\"age\"\r\n\"Ada \"\"A\"\"\", 017 \r\n\"Grace\",\"40\"\r\n"),
+            "\"name\",\"age\",\"eligible\",\"status\",\"years_until_eligible\",\"rule_id\"\r\n\
+             \"Ada \"\"A\"\"\", 017 ,\"false\",\"NotYetEligible\",\"1\",\"default/18\"\r\n\
+             \"Grace\",\"40\",\"true\",\"Eligible\",\"0\",\"default/18\"\r\n"
+        );
+    }
+
+    #[test]
+    fn detects_the_delimiter_and_keeps_a_missing_final_terminator() {
+        assert_eq!(
+            annotate("name;age\nSmith, J;18"),
+            "name;age;eligible;status;years_until_eligible;rule_id\n\
+             Smith, J;18;true;JustEligible;0;default/18"
+        );
+        assert_eq!(
+            annotate("\"a;b\"\tage\n1\t30\n"),
+            "\"a;b\"\tage\teligible\tstatus\tyears_until_eligible\trule_id\n\
+             1\t30\ttrue\tEligible\t0\tdefault/18\n"
+        );
+    }
+
+    #[test]
+    fn leaves_invalid_rows_unannotated_and_keeps_blank_lines() {
+        assert_eq!(
+            annotate("age\r\nold\r\n\r\n20\r\n"),
+            "age,eligible,status,years_until_eligible,rule_id\r\n\
+             old,,,,\r\n\r\n\
+             20,true,Eligible,0,default/18\r\n"
+        );
+    }
+}
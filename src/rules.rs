@@ -0,0 +1,672 @@
+use std::cell::RefCell;
+
+use chrono::NaiveDate;
+use wasm_bindgen::prelude::*;
+
+use crate::dates::{self, LeapDayPolicy};
+use crate::declarative::{NamedCondition, RuleDefinition};
+use crate::error::EligibilityError;
+
+/// Voting age used by `age_comparator` when no jurisdiction is given.
+pub const DEFAULT_VOTING_AGE: u8 = 18;
+
+thread_local! {
+    /// Rules loaded from definition files, which replace a jurisdiction's built-in history.
+    static LOADED: RefCell<Vec<VotingAgeRule>> = const { RefCell::new(Vec::new()) };
+}
+
+struct RuleSpec {
+    jurisdiction: &'static str,
+    voting_age: u8,
+    effective_from: Option<(i32, u32, u32)>,
+    leap_day: LeapDayPolicy,
+    preregistration_age: Option<u8>,
+    registration_lead_months: u8,
+    primary_by_general: bool,
+    residency_days: Option<u16>,
+    registration_required: bool,
+    other_citizenships: &'static [&'static str],
+}
+
+const fn rule(jurisdiction: &'static str, voting_age: u8) -> RuleSpec {
+    RuleSpec {
+        jurisdiction,
+        voting_age,
+        effective_from: None,
+        leap_day: LeapDayPolicy::Mar1,
+        preregistration_age: None,
+        registration_lead_months: 0,
+        primary_by_general: false,
+        residency_days: None,
+        registration_required: true,
+        other_citizenships: &[],
+    }
+}
+
+impl RuleSpec {
+    /// The rule replaces the jurisdiction's previous one from this date.
+    const fn since(self, year: i32, month: u32, day: u32) -> Self {
+        RuleSpec {
+            effective_from: Some((year, month, day)),
+            ..self
+        }
+    }
+
+    const fn leap_day(self, leap_day: LeapDayPolicy) -> Self {
+        RuleSpec { leap_day, ..self }
+    }
+
+    /// Pre-registration opens at `age`; the record becomes active at the voting age.
+    const fn preregister(self, age: u8) -> Self {
+        RuleSpec {
+            preregistration_age: Some(age),
+            ..self
+        }
+    }
+
+    /// Voters below the voting age may vote in a primary if they reach it by the general
+    /// election the primary nominates for.
+    const fn primary_by_general(self) -> Self {
+        RuleSpec {
+            primary_by_general: true,
+            ..self
+        }
+    }
+
+    /// Full registration opens `months` before the voting-age birthday.
+    const fn register_before(self, months: u8) -> Self {
+        RuleSpec {
+            registration_lead_months: months,
+            ..self
+        }
+    }
+
+    /// Voters must have lived in the jurisdiction for `days` before the election.
+    const fn residency(self, days: u16) -> Self {
+        RuleSpec {
+            residency_days: Some(days),
+            ..self
+        }
+    }
+
+    /// Voters are on the register automatically, or there is no register at all.
+    const fn no_registration(self) -> Self {
+        RuleSpec {
+            registration_required: false,
+            ..self
+        }
+    }
+
+    /// Citizens of `countries` may vote as well as the jurisdiction's own citizens.
+    const fn also_citizens(self, countries: &'static [&'static str]) -> Self {
+        RuleSpec {
+            other_citizenships: countries,
+            ..self
+        }
+    }
+}
+
+/// Commonwealth countries whose citizens may vote in UK elections when resident.
+const COMMONWEALTH: &[&str] = &[
+    "AG", "AU", "BB", "BD", "BS", "BW", "BZ", "CA", "CM", "CY", "DM", "FJ", "GA", "GD", "GH", "GM",
+    "GY", "IE", "IN", "JM", "KE", "KI", "KN", "LC", "LK", "LS", "MT", "MU", "MV", "MW", "MY", "MZ",
+    "NA", "NG", "NR", "NZ", "PG", "PK", "RW", "SB", "SC", "SG", "SL", "SZ", "TG", "TO", "TT", "TV",
+    "TZ", "UG", "VC", "VU", "WS", "ZA", "ZM",
+];
+
+/// Built-in voting ages keyed by ISO 3166-1 country or ISO 3166-2 subdivision code, with
+/// the pre-registration age and registration window where voters can sign up early, and
+/// the residency, registration and citizenship requirements beyond age.
+///
+/// A jurisdiction's rules are listed oldest first; each dated rule ends the one before it.
+/// A subdivision whose first rule is dated follows its country's rules until then.
+const BUILTIN_RULES: &[RuleSpec] = &[
+    rule("AR", 18),
+    rule("AR", 16).since(2012, 11, 1),
+    rule("AT", 18),
+    rule("AT", 16).since(2007, 7, 1),
+    rule("AU", 18).preregister(16),
+    rule("BD", 18),
+    rule("BE", 18),
+    rule("BH", 20),
+    rule("BR", 18),
+    rule("BR", 16).since(1988, 10, 5),
+    rule("CA", 18).preregister(14),
+    rule("CH", 18),
+    rule("CM", 20),
+    rule("CN", 18),
+    rule("CU", 16),
+    rule("CZ", 18),
+    rule("DE", 18).no_registration(),
+    rule("DE-BB", 16),
+    rule("DE-HB", 16),
+    rule("DE-HH", 16),
+    rule("DE-SH", 16),
+    rule("DK", 18).no_registration(),
+    rule("EC", 18),
+    rule("EC", 16).since(2008, 10, 20),
+    rule("EG", 18),
+    rule("ES", 18),
+    rule("FI", 18).no_registration(),
+    rule("FR", 18),
+    rule("GB", 18).preregister(16).also_citizens(COMMONWEALTH),
+    rule("GB-SCT", 16)
+        .since(2015, 6, 18)
+        .preregister(14)
+        .also_citizens(COMMONWEALTH),
+    rule("GB-WLS", 16)
+        .since(2020, 6, 1)
+        .preregister(14)
+        .also_citizens(COMMONWEALTH),
+    rule("GR", 17),
+    rule("ID", 17),
+    rule("IE", 18).also_citizens(&["GB"]),
+    rule("IL", 18),
+    rule("IM", 16),
+    rule("IN", 18),
+    rule("IT", 18),
+    rule("JP", 20),
+    rule("JP", 18).since(2016, 6, 19),
+    rule("KE", 18),
+    rule("KP", 17),
+    rule("KR", 20),
+    rule("KR", 19).since(2005, 8, 4),
+    rule("KR", 18).since(2020, 1, 14),
+    rule("KW", 21),
+    rule("LB", 21),
+    rule("MT", 16),
+    rule("MX", 18),
+    rule("NG", 18),
+    rule("NI", 16),
+    rule("NL", 18).no_registration(),
+    rule("NO", 18).no_registration(),
+    rule("NR", 20),
+    rule("NZ", 18)
+        .leap_day(LeapDayPolicy::Feb28)
+        .preregister(17),
+    rule("OM", 21),
+    rule("PH", 18),
+    rule("PK", 18),
+    rule("PL", 18),
+    rule("PT", 18),
+    rule("RU", 18),
+    rule("SE", 18).no_registration(),
+    rule("SG", 21),
+    rule("TK", 21),
+    rule("TL", 17),
+    rule("TR", 18),
+    rule("TW", 20).leap_day(LeapDayPolicy::Feb28),
+    rule("US", 18).residency(30),
+    rule("US-CA", 18).preregister(16).residency(15),
+    rule("US-CO", 18).preregister(16).residency(22),
+    rule("US-CT", 18).primary_by_general().residency(30),
+    rule("US-DC", 18)
+        .preregister(16)
+        .primary_by_general()
+        .residency(30),
+    rule("US-DE", 18)
+        .preregister(16)
+        .primary_by_general()
+        .residency(30),
+    rule("US-FL", 18).preregister(16).residency(30),
+    rule("US-GA", 18).register_before(6).residency(30),
+    rule("US-HI", 18).preregister(16).residency(30),
+    rule("US-IA", 18).register_before(6).residency(30),
+    rule("US-IL", 18).primary_by_general().residency(30),
+    rule("US-IN", 18).primary_by_general().residency(30),
+    rule("US-KY", 18).primary_by_general().residency(30),
+    rule("US-LA", 18).preregister(16).residency(30),
+    rule("US-MA", 18).preregister(16).residency(30),
+    rule("US-MD", 18)
+        .preregister(16)
+        .primary_by_general()
+        .residency(30),
+    rule("US-ME", 18).primary_by_general().residency(30),
+    rule("US-MS", 18).primary_by_general().residency(30),
+    rule("US-NC", 18).primary_by_general().residency(30),
+    rule("US-ND", 18).residency(30).no_registration(),
+    rule("US-NE", 18).primary_by_general().residency(30),
+    rule("US-NJ", 18).register_before(12).residency(30),
+    rule("US-NM", 18).primary_by_general().residency(30),
+    rule("US-NV", 18).preregister(16).residency(30),
+    rule("US-NY", 18).preregister(16).residency(30),
+    rule("US-OH", 18).primary_by_general().residency(30),
+    rule("US-OR", 18).preregister(16).residency(30),
+    rule("US-RI", 18).preregister(16).residency(30),
+    rule("US-SC", 18).primary_by_general().residency(30),
+    rule("US-TX", 18).register_before(2).residency(30),
+    rule("US-UT", 18)
+        .preregister(16)
+        .primary_by_general()
+        .residency(30),
+    rule("US-VA", 18)
+        .preregister(16)
+        .primary_by_general()
+        .residency(30),
+    rule("US-VT", 18).primary_by_general().residency(30),
+    rule("US-WA", 18).preregister(16).residency(30),
+    rule("US-WV", 18).primary_by_general().residency(30),
+    rule("WS", 21),
+    rule("ZA", 18),
+];
+
+/// A voting-age rule that was applied to an eligibility check.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VotingAgeRule {
+    id: String,
+    jurisdiction: String,
+    voting_age: u8,
+    effective_from: Option<NaiveDate>,
+    effective_to: Option<NaiveDate>,
+    leap_day: LeapDayPolicy,
+    preregistration_age: Option<u8>,
+    registration_lead_months: u8,
+    primary_by_general: bool,
+    residency_days: Option<u16>,
+    registration_required: bool,
+    accepted_citizenships: Vec<String>,
+    conditions: Vec<NamedCondition>,
+}
+
+#[wasm_bindgen]
+impl VotingAgeRule {
+    #[wasm_bindgen(getter)]
+    pub fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn jurisdiction(&self) -> String {
+        self.jurisdiction.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = votingAge)]
+    pub fn voting_age(&self) -> u8 {
+        self.voting_age
+    }
+
+    /// First day the rule applied, as `YYYY-MM-DD`, or `undefined` for the oldest known rule.
+    #[wasm_bindgen(getter, js_name = effectiveFrom)]
+    pub fn iso_effective_from(&self) -> Option<String> {
+        self.effective_from.map(|date| date.to_string())
+    }
+
+    /// Last day the rule applied, as `YYYY-MM-DD`, or `undefined` while it is still in force.
+    #[wasm_bindgen(getter, js_name = effectiveTo)]
+    pub fn iso_effective_to(&self) -> Option<String> {
+        self.effective_to.map(|date| date.to_string())
+    }
+
+    /// Whether the rule was in force on `date`, an ISO-8601 string or JS `Date`.
+    #[wasm_bindgen(js_name = inForceOn)]
+    pub fn js_in_force_on(&self, date: JsValue) -> Result<bool, JsError> {
+        Ok(self.in_force_on(dates::date_from_js(&date)?))
+    }
+
+    #[wasm_bindgen(getter, js_name = leapDay)]
+    pub fn leap_day(&self) -> LeapDayPolicy {
+        self.leap_day
+    }
+
+    /// Age from which voters can pre-register, if the jurisdiction allows it.
+    #[wasm_bindgen(getter, js_name = preregistrationAge)]
+    pub fn preregistration_age(&self) -> Option<u8> {
+        self.preregistration_age
+    }
+
+    /// Months before the voting-age birthday from which voters can register in full.
+    #[wasm_bindgen(getter, js_name = registrationLeadMonths)]
+    pub fn registration_lead_months(&self) -> u8 {
+        self.registration_lead_months
+    }
+
+    /// Whether under-age voters may vote in a primary if they reach the voting age by the
+    /// linked general election.
+    #[wasm_bindgen(getter, js_name = primaryByGeneral)]
+    pub fn primary_by_general(&self) -> bool {
+        self.primary_by_general
+    }
+
+    /// Days a voter must have lived in the jurisdiction, where a minimum applies.
+    #[wasm_bindgen(getter, js_name = residencyDays)]
+    pub fn residency_days(&self) -> Option<u16> {
+        self.residency_days
+    }
+
+    /// Whether voters must be on the electoral register to vote.
+    #[wasm_bindgen(getter, js_name = registrationRequired)]
+    pub fn registration_required(&self) -> bool {
+        self.registration_required
+    }
+
+    /// ISO 3166-1 codes of the citizenships that carry the vote, own country first.
+    #[wasm_bindgen(getter, js_name = acceptedCitizenships)]
+    pub fn accepted_citizenships(&self) -> Vec<String> {
+        self.accepted_citizenships.clone()
+    }
+}
+
+impl VotingAgeRule {
+    /// The rule `age_comparator` falls back to when called without a jurisdiction.
+    pub fn default_rule() -> Self {
+        VotingAgeRule {
+            id: format!("default/{}", DEFAULT_VOTING_AGE),
+            jurisdiction: String::new(),
+            voting_age: DEFAULT_VOTING_AGE,
+            effective_from: None,
+            effective_to: None,
+            leap_day: LeapDayPolicy::Mar1,
+            preregistration_age: None,
+            registration_lead_months: 0,
+            primary_by_general: false,
+            residency_days: None,
+            registration_required: true,
+            accepted_citizenships: Vec::new(),
+            conditions: Vec::new(),
+        }
+    }
+
+    /// This rule with its voting age replaced, recording `source` (e.g. an election id) in the id.
+    pub fn with_voting_age(&self, voting_age: u8, source: &str) -> Self {
+        VotingAgeRule {
+            id: format!("{}/{}", source, voting_age),
+            voting_age,
+            ..self.clone()
+        }
+    }
+
+    pub fn effective_from(&self) -> Option<NaiveDate> {
+        self.effective_from
+    }
+
+    pub fn effective_to(&self) -> Option<NaiveDate> {
+        self.effective_to
+    }
+
+    /// Conditions a loaded rule definition adds to the built-in criteria.
+    pub fn conditions(&self) -> &[NamedCondition] {
+        &self.conditions
+    }
+
+    /// Whether `date` falls within the rule's effective dates, both ends included.
+    pub fn in_force_on(&self, date: NaiveDate) -> bool {
+        self.effective_from.is_none_or(|from| from <= date)
+            && self.effective_to.is_none_or(|to| date <= to)
+    }
+
+    /// Ends the rule the day before `date`.
+    fn superseded_on(mut self, date: NaiveDate) -> Self {
+        let last = date
+            .pred_opt()
+            .expect("rule dates are far from the minimum date");
+        self.effective_to = Some(self.effective_to.map_or(last, |to| to.min(last)));
+        self
+    }
+
+    fn from_spec(spec: &RuleSpec) -> Self {
+        let effective_from = spec.effective_from.map(|(year, month, day)| {
+            NaiveDate::from_ymd_opt(year, month, day).expect("built-in rule dates are valid")
+        });
+        let id = match effective_from {
+            Some(from) => format!("{}/{}@{}", spec.jurisdiction, spec.voting_age, from),
+            None => format!("{}/{}", spec.jurisdiction, spec.voting_age),
+        };
+        VotingAgeRule {
+            id,
+            jurisdiction: spec.jurisdiction.to_string(),
+            voting_age: spec.voting_age,
+            effective_from,
+            effective_to: None,
+            leap_day: spec.leap_day,
+            preregistration_age: spec.preregistration_age,
+            registration_lead_months: spec.registration_lead_months,
+            primary_by_general: spec.primary_by_general,
+            residency_days: spec.residency_days,
+            registration_required: spec.registration_required,
+            accepted_citizenships: spec
+                .jurisdiction
+                .split('-')
+                .take(1)
+                .chain(spec.other_citizenships.iter().copied())
+                .map(str::to_string)
+                .collect(),
+            conditions: Vec::new(),
+        }
+    }
+
+    pub(crate) fn from_definition(definition: &RuleDefinition) -> Self {
+        let code = &definition.jurisdiction;
+        let id = match (&definition.id, definition.effective_from) {
+            (Some(id), _) => id.clone(),
+            (None, Some(from)) => format!("{}/{}@{}", code, definition.voting_age, from),
+            (None, None) => format!("{}/{}", code, definition.voting_age),
+        };
+        VotingAgeRule {
+            id,
+            jurisdiction: code.clone(),
+            voting_age: definition.voting_age,
+            effective_from: definition.effective_from,
+            effective_to: None,
+            leap_day: definition.leap_day(),
+            preregistration_age: definition.preregistration_age,
+            registration_lead_months: definition.registration_lead_months,
+            primary_by_general: definition.primary_by_general,
+            residency_days: definition.residency_days,
+            registration_required: definition.registration_required,
+            accepted_citizenships: code
+                .split('-')
+                .take(1)
+                .map(str::to_string)
+                .chain(
+                    definition
+                        .other_citizenships
+                        .iter()
+                        .map(|country| country.trim().to_ascii_uppercase()),
+                )
+                .collect(),
+            conditions: definition.conditions.clone(),
+        }
+    }
+}
+
+/// Normalizes a user-supplied code such as `us_me` or ` gb-sct ` to `US-ME` / `GB-SCT`.
+pub fn normalize_jurisdiction(code: &str) -> String {
+    code.trim().replace('_', "-").to_ascii_uppercase()
+}
+
+/// Puts loaded rules in force, replacing every earlier rule for their jurisdictions.
+pub fn register(rules: Vec<VotingAgeRule>) {
+    LOADED.with(|loaded| {
+        let mut loaded = loaded.borrow_mut();
+        loaded.retain(|rule| {
+            !rules
+                .iter()
+                .any(|new| new.jurisdiction == rule.jurisdiction)
+        });
+        loaded.extend(rules);
+    });
+}
+
+/// Drops every loaded rule.
+pub fn reset() {
+    LOADED.with(|loaded| loaded.borrow_mut().clear());
+}
+
+/// Ids of the loaded rules.
+pub fn registered() -> Vec<String> {
+    LOADED.with(|loaded| loaded.borrow().iter().map(VotingAgeRule::id).collect())
+}
+
+/// The rules of exactly `code`, loaded ones instead of built-in ones, oldest first, each
+/// ended by its successor.
+fn own_history(code: &str) -> Vec<VotingAgeRule> {
+    let mut history: Vec<VotingAgeRule> = LOADED.with(|loaded| {
+        loaded
+            .borrow()
+            .iter()
+            .filter(|rule| rule.jurisdiction == code)
+            .cloned()
+            .collect()
+    });
+    if history.is_empty() {
+        history = BUILTIN_RULES
+            .iter()
+            .filter(|spec| spec.jurisdiction == code)
+            .map(VotingAgeRule::from_spec)
+            .collect();
+    }
+    history.sort_by_key(|rule| rule.effective_from);
+    for index in 1..history.len() {
+        if let Some(from) = history[index].effective_from {
+            history[index - 1] = history[index - 1].clone().superseded_on(from);
+        }
+    }
+    history
+}
+
+/// Every rule that has applied in a jurisdiction, oldest first.
+///
+/// A subdivision's history starts with its country's rules up to the day its own first rule
+/// took effect, so the rule in force on any date is in the list.
+pub fn history(jurisdiction: &str) -> Result<Vec<VotingAgeRule>, EligibilityError> {
+    let code = normalize_jurisdiction(jurisdiction);
+    let country = code.split('-').next().unwrap_or_default();
+
+    let own = own_history(&code);
+    let mut history = Vec::new();
+    if country != code {
+        match own.first().map(|rule| rule.effective_from) {
+            Some(None) => {}
+            Some(Some(start)) => history.extend(
+                own_history(country)
+                    .into_iter()
+                    .filter(|rule| rule.effective_from.is_none_or(|from| from < start))
+                    .map(|rule| rule.superseded_on(start)),
+            ),
+            None => history.extend(own_history(country)),
+        }
+    }
+    history.extend(own);
+    if history.is_empty() {
+        return Err(EligibilityError::UnknownJurisdiction(code));
+    }
+    Ok(history)
+}
+
+/// Looks up the rule in force today, falling back from a subdivision to its country.
+pub fn lookup(jurisdiction: &str) -> Result<VotingAgeRule, EligibilityError> {
+    lookup_as_of(jurisdiction, dates::today())
+}
+
+/// The rule that was in force in a jurisdiction on `date`.
+pub fn lookup_as_of(
+    jurisdiction: &str,
+    date: NaiveDate,
+) -> Result<VotingAgeRule, EligibilityError> {
+    history(jurisdiction)?
+        .into_iter()
+        .find(|rule| rule.in_force_on(date))
+        .ok_or_else(|| EligibilityError::NoRuleInForce {
+            jurisdiction: normalize_jurisdiction(jurisdiction),
+            on: date,
+        })
+}
+
+/// Every voting-age rule that has applied in `jurisdiction`, oldest first.
+///
+/// Each rule's `effectiveFrom` and `effectiveTo` show the dates it covered, so an audit can
+/// tell which one applied to a past election.
+#[wasm_bindgen]
+pub fn rule_history(jurisdiction: &str) -> Result<Vec<VotingAgeRule>, JsError> {
+    Ok(history(jurisdiction)?)
+}
+
+/// The voting-age rule in force in `jurisdiction` on `as_of`, or today's rule without a date.
+#[wasm_bindgen]
+pub fn rule_in_force(jurisdiction: &str, as_of: JsValue) -> Result<VotingAgeRule, JsError> {
+    if as_of.is_undefined() || as_of.is_null() {
+        return Ok(lookup(jurisdiction)?);
+    }
+    Ok(lookup_as_of(jurisdiction, dates::date_from_js(&as_of)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::declarative::{self, RuleFormat};
+
+    fn date(text: &str) -> NaiveDate {
+        dates::parse_iso_date(text).unwrap()
+    }
+
+    #[test]
+    fn normalizes_codes() {
+        assert_eq!(normalize_jurisdiction(" us_me "), "US-ME");
+        assert_eq!(normalize_jurisdiction("gb-sct"), "GB-SCT");
+    }
+
+    #[test]
+    fn looks_up_current_rules_with_subdivision_fallback() {
+        assert_eq!(lookup("AT").unwrap().voting_age(), 16);
+        assert_eq!(lookup("gb_sct").unwrap().voting_age(), 16);
+        assert_eq!(lookup("GB-ENG").unwrap().id(), lookup("GB").unwrap().id());
+        assert!(matches!(
+            lookup("ZZ"),
+            Err(EligibilityError::UnknownJurisdiction(code)) if code == "ZZ"
+        ));
+    }
+
+    #[test]
+    fn finds_the_rule_in_force_on_a_date() {
+        let age_on = |code: &str, on: &str| lookup_as_of(code, date(on)).unwrap().voting_age();
+        assert_eq!(age_on("JP", "2016-06-18"), 20);
+        assert_eq!(age_on("JP", "2016-06-19"), 18);
+        assert_eq!(age_on("GB-SCT", "2015-06-17"), 18);
+        assert_eq!(age_on("GB-SCT", "2015-06-18"), 16);
+    }
+
+    #[test]
+    fn subdivision_history_starts_with_the_country() {
+        let history = history("GB-SCT").unwrap();
+        let ages: Vec<_> = history.iter().map(VotingAgeRule::voting_age).collect();
+        assert_eq!(ages, [18, 16]);
+        assert_eq!(history[0].jurisdiction(), "GB");
+        assert_eq!(history[0].iso_effective_to().as_deref(), Some("2015-06-17"));
+        assert_eq!(history[1].iso_effective_to(), None);
+    }
+
+    #[test]
+    fn loaded_rules_replace_the_builtin_history() {
+        declarative::load(
+            r#"{"rules": [
+                {"jurisdiction": "FR", "votingAge": 18},
+                {"jurisdiction": "FR", "effectiveFrom": "2030-01-01", "votingAge": 16}
+            ]}"#,
+            RuleFormat::Json,
+        )
+        .unwrap();
+        assert_eq!(registered(), ["FR/18", "FR/16@2030-01-01"]);
+        assert_eq!(
+            lookup_as_of("FR", date("2029-12-31")).unwrap().voting_age(),
+            18
+        );
+        // The 2030 rule is scheduled, not yet in force.
+        assert_eq!(lookup("FR").unwrap().voting_age(), 18);
+
+        reset();
+        assert!(registered().is_empty());
+        assert_eq!(history("FR").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn builtin_rules_carry_registration_and_citizenship() {
+        let gb = lookup("GB").unwrap();
+        assert_eq!(gb.preregistration_age(), Some(16));
+        let citizenships = gb.accepted_citizenships();
+        assert_eq!(citizenships[0], "GB");
+        assert!(citizenships.iter().any(|code| code == "IE"));
+        assert!(!lookup("DE").unwrap().registration_required());
+        assert_eq!(lookup("US-ND").unwrap().residency_days(), Some(30));
+    }
+}
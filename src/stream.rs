@@ -0,0 +1,315 @@
+use csv::StringRecord;
+use csv_core::{ReadRecordResult, Reader, ReaderBuilder};
+use wasm_bindgen::prelude::*;
+
+use crate::error::EligibilityError;
+use crate::js;
+use crate::roll::{RollOptions, RollReport, RollSummary, RowChecker};
+use crate::validation::{RowError, ValidationErrorKind};
+
+/// Incremental voter-roll checker fed one chunk at a time, e.g. from `fs.createReadStream`.
+///
+/// Records may be split across chunks; each `push` reports only the records it completed.
+#[wasm_bindgen]
+pub struct RollStream {
+    options: RollOptions,
+    parser: Reader,
+    checker: Option<RowChecker>,
+    fields: Vec<u8>,
+    ends: Vec<usize>,
+    field_len: usize,
+    ends_len: usize,
+    finished: bool,
+    summary: RollSummary,
+    partial: Option<RollReport>,
+}
+
+impl RollStream {
+    pub fn new(options: RollOptions) -> Result<Self, EligibilityError> {
+        let parser = ReaderBuilder::new()
+            .delimiter(options.delimiter_byte()?)
+            .build();
+        Ok(RollStream {
+            options,
+            parser,
+            checker: None,
+            fields: vec![0; 1024],
+            ends: vec![0; 32],
+            field_len: 0,
+            ends_len: 0,
+            finished: false,
+            summary: RollSummary::default(),
+            partial: None,
+        })
+    }
+
+    /// Parses `chunk` and checks every record it completes. Empty chunks are ignored.
+    pub fn push_bytes(&mut self, chunk: &[u8]) -> Result<RollReport, EligibilityError> {
+        if self.finished {
+            return Err(EligibilityError::StreamFinished);
+        }
+        if chunk.is_empty() {
+            return Ok(RollReport::default());
+        }
+        self.read(chunk)
+    }
+
+    /// Flushes a final record that was not followed by a line terminator.
+    pub fn finish_report(&mut self) -> Result<RollReport, EligibilityError> {
+        if self.finished {
+            return Err(EligibilityError::StreamFinished);
+        }
+        let report = self.read(&[])?;
+        self.finished = true;
+        if self.checker.is_none() {
+            return Err(EligibilityError::Csv("input has no header row".to_string()));
+        }
+        Ok(report)
+    }
+
+    /// The rows the failing chunk completed before a fail-fast error, once one has occurred.
+    pub fn partial_report(&self) -> Option<&RollReport> {
+        self.partial.as_ref()
+    }
+
+    /// Runs the parser over `input`; an empty `input` signals the end of the data.
+    ///
+    /// A fail-fast error still counts the rows checked before it, keeps them as the partial
+    /// report and ends the stream.
+    fn read(&mut self, input: &[u8]) -> Result<RollReport, EligibilityError> {
+        let mut report = RollReport::default();
+        let outcome = self.read_into(input, &mut report);
+        self.summary.merge(&report.summary);
+        if let Err(error) = outcome {
+            self.finished = true;
+            self.partial = Some(report);
+            return Err(error);
+        }
+        Ok(report)
+    }
+
+    fn read_into(
+        &mut self,
+        mut input: &[u8],
+        report: &mut RollReport,
+    ) -> Result<(), EligibilityError> {
+        loop {
+            let (result, read, written, ended) = self.parser.read_record(
+                input,
+                &mut self.fields[self.field_len..],
+                &mut self.ends[self.ends_len..],
+            );
+            let terminator = input[..read].last().copied();
+            input = &input[read..];
+            self.field_len += written;
+            self.ends_len += ended;
+
+            match result {
+                ReadRecordResult::InputEmpty => break,
+                ReadRecordResult::OutputFull => {
+                    let len = self.fields.len();
+                    self.fields.resize(len * 2, 0);
+                }
+                ReadRecordResult::OutputEndsFull => {
+                    let len = self.ends.len();
+                    self.ends.resize(len * 2, 0);
+                }
+                ReadRecordResult::Record => {
+                    let line = self.record_line(terminator);
+                    self.complete_record(line, report)?;
+                }
+                ReadRecordResult::End => return Ok(()),
+            }
+        }
+        Ok(())
+    }
+
+    /// The line the record just read starts on, given the last byte read with it.
+    ///
+    /// The parser has counted every `\n` up to the end of the record, including an LF terminator
+    /// but not the `\n` of a CRLF one, so the record's own newlines are taken off.
+    fn record_line(&self, terminator: Option<u8>) -> u64 {
+        let newlines = self.fields[..self.field_len]
+            .iter()
+            .filter(|&&byte| byte == b'\n')
+            .count() as u64;
+        let terminated = u64::from(terminator == Some(b'\n'));
+        self.parser.line().saturating_sub(newlines + terminated)
+    }
+
+    fn complete_record(
+        &mut self,
+        line: u64,
+        report: &mut RollReport,
+    ) -> Result<(), EligibilityError> {
+        let record = self.take_record(line);
+        let outcome = match (&mut self.checker, record) {
+            (None, Ok(headers)) => {
+                self.checker = Some(RowChecker::new(&headers, &self.options)?);
+                return Ok(());
+            }
+            (None, Err(error)) => return Err(EligibilityError::InvalidRow(Box::new(error))),
+            (Some(checker), Ok(record)) => checker.check(line, &record),
+            (Some(_), Err(error)) => Err(error),
+        };
+        report.push(outcome, self.options.error_mode)
+    }
+
+    fn take_record(&mut self, line: u64) -> Result<StringRecord, RowError> {
+        let fields = &self.fields[..self.field_len];
+        let ends = &self.ends[..self.ends_len];
+        let record = std::str::from_utf8(fields)
+            .map(|text| {
+                let mut start = 0;
+                let mut record = StringRecord::with_capacity(text.len(), ends.len());
+                for &end in ends {
+                    record.push_field(&text[start..end]);
+                    start = end;
+                }
+                record
+            })
+            .map_err(|error| {
+                RowError::new(
+                    line,
+                    "",
+                    &format!("invalid UTF-8: {}", error),
+                    ValidationErrorKind::MalformedRecord,
+                )
+            });
+        self.field_len = 0;
+        self.ends_len = 0;
+        record
+    }
+}
+
+#[wasm_bindgen]
+impl RollStream {
+    #[wasm_bindgen(constructor)]
+    pub fn js_new(options: JsValue) -> Result<RollStream, JsError> {
+        Ok(RollStream::new(js::options_from_js(options)?)?)
+    }
+
+    /// Feeds the next chunk and returns `{ rows, errors, summary }` for the records it completed.
+    pub fn push(&mut self, chunk: &[u8]) -> Result<JsValue, JsError> {
+        js::to_js(&self.push_bytes(chunk)?)
+    }
+
+    /// Ends the stream and returns the report for any trailing record.
+    pub fn finish(&mut self) -> Result<JsValue, JsError> {
+        js::to_js(&self.finish_report()?)
+    }
+
+    /// Counts across every chunk pushed so far.
+    #[wasm_bindgen(getter)]
+    pub fn summary(&self) -> Result<JsValue, JsError> {
+        js::to_js(&self.summary)
+    }
+
+    /// After a fail-fast error, `{ rows, errors, summary }` for the records the failing chunk
+    /// completed before it; `undefined` otherwise.
+    #[wasm_bindgen(getter)]
+    pub fn partial(&self) -> Result<JsValue, JsError> {
+        match &self.partial {
+            Some(report) => js::to_js(report),
+            None => Ok(JsValue::UNDEFINED),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roll;
+    use crate::validation::ErrorMode;
+
+    const ROLL: &str = "\u{feff}name,age\r\n\"Lovelace, Ada\",36\r\n\"Line\r\nBreak\",17\r\n\r\nAlan,x\r\nGrace,\"18\"";
+
+    fn rows(report: &RollReport) -> Vec<(u64, Option<String>)> {
+        report
+            .rows
+            .iter()
+            .map(|row| (row.row, row.name.clone()))
+            .collect()
+    }
+
+    fn stream_in_chunks(input: &[u8], size: usize, options: RollOptions) -> RollReport {
+        let mut stream = RollStream::new(options).unwrap();
+        let mut report = RollReport::default();
+        for chunk in input.chunks(size) {
+            let pushed = stream.push_bytes(chunk).unwrap();
+            report.rows.extend(pushed.rows);
+            report.errors.extend(pushed.errors);
+        }
+        let last = stream.finish_report().unwrap();
+        report.rows.extend(last.rows);
+        report.errors.extend(last.errors);
+        report.summary = stream.summary.clone();
+        report
+    }
+
+    #[test]
+    fn chunk_boundaries_do_not_change_the_report() {
+        let whole = roll::check_roll(ROLL, &RollOptions::default()).unwrap();
+        assert_eq!(
+            rows(&whole),
+            [
+                (2, Some("Lovelace, Ada".to_string())),
+                (3, Some("Line\r\nBreak".to_string())),
+                (7, Some("Grace".to_string())),
+            ]
+        );
+        for size in 1..=ROLL.len() {
+            let streamed = stream_in_chunks(ROLL.as_bytes(), size, RollOptions::default());
+            assert_eq!(rows(&streamed), rows(&whole), "chunks of {} bytes", size);
+            assert_eq!(streamed.errors, whole.errors, "chunks of {} bytes", size);
+            assert_eq!(streamed.summary.total, 4);
+            assert_eq!(streamed.summary.invalid, 1);
+        }
+    }
+
+    #[test]
+    fn lf_rows_are_numbered_like_the_whole_roll() {
+        let input = ROLL.replace("\r\n", "\n");
+        let whole = roll::check_roll(&input, &RollOptions::default()).unwrap();
+        let streamed = stream_in_chunks(input.as_bytes(), 5, RollOptions::default());
+        assert_eq!(rows(&streamed), rows(&whole));
+        assert_eq!(streamed.errors, whole.errors);
+    }
+
+    #[test]
+    fn fail_fast_keeps_earlier_rows_and_ends_the_stream() {
+        let options = RollOptions {
+            error_mode: ErrorMode::FailFast,
+            ..RollOptions::default()
+        };
+        let mut stream = RollStream::new(options).unwrap();
+        assert!(stream.push_bytes(b"age\n2").unwrap().rows.is_empty());
+        assert!(stream.partial_report().is_none());
+        let error = stream.push_bytes(b"0\n17\n19\nold\n30\n").unwrap_err();
+        assert!(matches!(error, EligibilityError::InvalidRow(ref row) if row.row == 5));
+        let partial = stream.partial_report().unwrap();
+        assert_eq!(rows(partial), [(2, None), (3, None), (4, None)]);
+        assert_eq!(partial.summary.total, 4);
+        assert_eq!(stream.summary.total, 4);
+        assert_eq!(stream.summary.eligible, 2);
+        assert_eq!(stream.summary.invalid, 1);
+        assert_eq!(
+            stream.push_bytes(b"40\n").unwrap_err(),
+            EligibilityError::StreamFinished
+        );
+        assert_eq!(
+            stream.finish_report().unwrap_err(),
+            EligibilityError::StreamFinished
+        );
+    }
+
+    #[test]
+    fn finishing_without_a_header_is_an_error() {
+        let mut stream = RollStream::new(RollOptions::default()).unwrap();
+        assert!(stream.push_bytes(b"").unwrap().rows.is_empty());
+        assert!(matches!(
+            stream.finish_report(),
+            Err(EligibilityError::Csv(_))
+        ));
+    }
+}
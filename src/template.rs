@@ -0,0 +1,679 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::catalog::{self, Arg};
+use crate::js;
+use crate::locale::{self, LanguageTag, DEFAULT_LOCALE};
+use crate::numbers;
+use crate::plural::{self, PluralCategory};
+
+thread_local! {
+    /// Templates registered from JS, keyed by normalized locale and message id.
+    static REGISTERED: RefCell<HashMap<(String, String), Rc<Template>>> =
+        RefCell::new(HashMap::new());
+    /// Built-in catalog patterns, compiled on first use.
+    static BUILTIN: RefCell<HashMap<&'static str, Rc<Template>>> = RefCell::new(HashMap::new());
+}
+
+/// A template that failed to compile, with the character offset of the problem.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TemplateError {
+    pub message: String,
+    pub offset: usize,
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at character {}", self.message, self.offset)
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Filter {
+    Upper,
+    Lower,
+    Title,
+    Trim,
+    Ordinal,
+    /// Picks a word by the plural category of a number.
+    Plural(Vec<(PluralCategory, String)>),
+    /// Replaces a missing or empty value.
+    Default(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Text(String),
+    Value {
+        name: String,
+        /// Character offset of the tag in the source.
+        offset: usize,
+        filters: Vec<Filter>,
+    },
+    If {
+        name: String,
+        offset: usize,
+        negate: bool,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+}
+
+/// A compiled template.
+///
+/// `{name}` inserts a value and `{name|upper|title}` pipes it through filters: `upper`,
+/// `lower`, `title`, `trim`, `ordinal`, `default:text` and `plural:year:years` (or keyed by
+/// CLDR category, `plural:one=год:few=года:many=лет`). `{#if name}…{#else}…{/if}` tests a
+/// value for being non-empty and non-zero, `{#if !name}` negates it, and `{{`/`}}` are
+/// literal braces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+struct Frame {
+    name: String,
+    negate: bool,
+    offset: usize,
+    then: Option<Vec<Node>>,
+    nodes: Vec<Node>,
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn parse_filter(spec: &str) -> Result<Filter, String> {
+    let mut parts = spec.split(':').map(str::trim);
+    let name = parts.next().unwrap_or_default();
+    let args: Vec<&str> = parts.collect();
+    let no_args = |filter| {
+        if args.is_empty() {
+            Ok(filter)
+        } else {
+            Err(format!("filter `{}` takes no arguments", name))
+        }
+    };
+    match name {
+        "upper" => no_args(Filter::Upper),
+        "lower" => no_args(Filter::Lower),
+        "title" => no_args(Filter::Title),
+        "trim" => no_args(Filter::Trim),
+        "ordinal" => no_args(Filter::Ordinal),
+        "default" => Ok(Filter::Default(args.join(":"))),
+        "plural" if args.iter().all(|arg| arg.contains('=')) && !args.is_empty() => {
+            let forms = args
+                .iter()
+                .map(|arg| {
+                    let (key, word) = arg.split_once('=').unwrap_or_default();
+                    PluralCategory::parse(key.trim())
+                        .map(|category| (category, word.to_string()))
+                        .ok_or_else(|| format!("`{}` is not a plural category", key))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if !forms
+                .iter()
+                .any(|(category, _)| *category == PluralCategory::Other)
+            {
+                return Err("`plural` needs an `other=` form".to_string());
+            }
+            Ok(Filter::Plural(forms))
+        }
+        "plural" if args.len() == 2 => Ok(Filter::Plural(vec![
+            (PluralCategory::One, args[0].to_string()),
+            (PluralCategory::Other, args[1].to_string()),
+        ])),
+        "plural" => Err("`plural` takes `one:other` or `category=word` forms".to_string()),
+        _ => Err(format!("unknown filter `{}`", name)),
+    }
+}
+
+impl Template {
+    pub fn compile(source: &str) -> Result<Self, TemplateError> {
+        let chars = |offset: usize| source[..offset].chars().count();
+        let error = |message: String, offset: usize| TemplateError {
+            message,
+            offset: chars(offset),
+        };
+        let mut stack = vec![Frame {
+            name: String::new(),
+            negate: false,
+            offset: 0,
+            then: None,
+            nodes: Vec::new(),
+        }];
+        let mut text = String::new();
+        let mut rest = source;
+
+        while let Some(brace) = rest.find(['{', '}']) {
+            let offset = source.len() - rest.len() + brace;
+            text.push_str(&rest[..brace]);
+            let after = &rest[brace + 1..];
+            if rest[brace..].starts_with("{{") || rest[brace..].starts_with("}}") {
+                text.push_str(&rest[brace..brace + 1]);
+                rest = &after[1..];
+                continue;
+            }
+            if rest[brace..].starts_with('}') {
+                return Err(error(
+                    "unmatched `}`; write `}}` for a brace".to_string(),
+                    offset,
+                ));
+            }
+            let close = after
+                .find('}')
+                .ok_or_else(|| error("unclosed `{`".to_string(), offset))?;
+            let tag = after[..close].trim();
+            rest = &after[close + 1..];
+
+            let nested = stack.len() > 1;
+            let frame = stack.last_mut().expect("the root frame is never popped");
+            if !text.is_empty() {
+                frame.nodes.push(Node::Text(std::mem::take(&mut text)));
+            }
+
+            if let Some(condition) = tag.strip_prefix("#if ") {
+                let condition = condition.trim();
+                let (negate, name) = match condition.strip_prefix('!') {
+                    Some(name) => (true, name.trim()),
+                    None => (false, condition),
+                };
+                if !is_identifier(name) {
+                    return Err(error(
+                        format!("`{}` is not a placeholder name", name),
+                        offset,
+                    ));
+                }
+                stack.push(Frame {
+                    name: name.to_string(),
+                    negate,
+                    offset,
+                    then: None,
+                    nodes: Vec::new(),
+                });
+            } else if tag == "#else" {
+                if !nested || frame.then.is_some() {
+                    return Err(error("`{#else}` outside `{#if}`".to_string(), offset));
+                }
+                frame.then = Some(std::mem::take(&mut frame.nodes));
+            } else if tag == "/if" {
+                if !nested {
+                    return Err(error("`{/if}` without `{#if}`".to_string(), offset));
+                }
+                let frame = stack.pop().expect("checked above");
+                let (then, otherwise) = match frame.then {
+                    Some(then) => (then, frame.nodes),
+                    None => (frame.nodes, Vec::new()),
+                };
+                stack
+                    .last_mut()
+                    .expect("checked above")
+                    .nodes
+                    .push(Node::If {
+                        name: frame.name,
+                        offset: chars(frame.offset),
+                        negate: frame.negate,
+                        then,
+                        otherwise,
+                    });
+            } else {
+                let mut parts = tag.split('|').map(str::trim);
+                let name = parts.next().unwrap_or_default();
+                if !is_identifier(name) {
+                    return Err(error(
+                        format!("`{}` is not a placeholder name", name),
+                        offset,
+                    ));
+                }
+                let filters = parts
+                    .map(parse_filter)
+                    .collect::<Result<_, _>>()
+                    .map_err(|message| error(message, offset))?;
+                frame.nodes.push(Node::Value {
+                    name: name.to_string(),
+                    offset: chars(offset),
+                    filters,
+                });
+            }
+        }
+
+        text.push_str(rest);
+        if stack.len() > 1 {
+            let open = stack.last().map_or(0, |frame| frame.offset);
+            return Err(error("`{#if}` without `{/if}`".to_string(), open));
+        }
+        let mut root = stack.pop().expect("the root frame is never popped");
+        if !text.is_empty() {
+            root.nodes.push(Node::Text(text));
+        }
+        Ok(Template { nodes: root.nodes })
+    }
+
+    /// Names of every placeholder and condition in the template.
+    pub fn placeholders(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.uses().into_iter().map(|(name, _)| name).collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// Every placeholder and condition with the character offset of its tag, in source order.
+    fn uses(&self) -> Vec<(&str, usize)> {
+        fn collect<'a>(nodes: &'a [Node], uses: &mut Vec<(&'a str, usize)>) {
+            for node in nodes {
+                match node {
+                    Node::Text(_) => {}
+                    Node::Value { name, offset, .. } => uses.push((name, *offset)),
+                    Node::If {
+                        name,
+                        offset,
+                        then,
+                        otherwise,
+                        ..
+                    } => {
+                        uses.push((name, *offset));
+                        collect(then, uses);
+                        collect(otherwise, uses);
+                    }
+                }
+            }
+        }
+        let mut uses = Vec::new();
+        collect(&self.nodes, &mut uses);
+        uses.sort_by_key(|&(_, offset)| offset);
+        uses
+    }
+
+    /// Renders with `args`, passing every inserted value through `escape`.
+    ///
+    /// Missing values render as `{name}` so they stand out.
+    pub fn render(
+        &self,
+        args: &[(&str, Arg)],
+        locale: &str,
+        escape: &dyn Fn(&str) -> String,
+    ) -> String {
+        let mut out = String::new();
+        self.write(&self.nodes, &mut out, args, locale, escape);
+        out
+    }
+
+    fn write(
+        &self,
+        nodes: &[Node],
+        out: &mut String,
+        args: &[(&str, Arg)],
+        locale: &str,
+        escape: &dyn Fn(&str) -> String,
+    ) {
+        let lookup = |name: &str| {
+            args.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| *value)
+        };
+        for node in nodes {
+            match node {
+                Node::Text(text) => out.push_str(text),
+                Node::Value { name, filters, .. } => match lookup(name) {
+                    Some(value) => out.push_str(&escape(&apply(value, filters, locale))),
+                    None => match filters.iter().find_map(|filter| match filter {
+                        Filter::Default(text) => Some(text),
+                        _ => None,
+                    }) {
+                        Some(text) => out.push_str(&escape(text)),
+                        None => {
+                            out.push('{');
+                            out.push_str(name);
+                            out.push('}');
+                        }
+                    },
+                },
+                Node::If {
+                    name,
+                    negate,
+                    then,
+                    otherwise,
+                    ..
+                } => {
+                    let truthy = match lookup(name) {
+                        Some(Arg::Text(text)) => !text.is_empty(),
+                        Some(Arg::Number(n)) => n != 0,
+                        None => false,
+                    };
+                    let branch = if truthy != *negate { then } else { otherwise };
+                    self.write(branch, out, args, locale, escape);
+                }
+            }
+        }
+    }
+}
+
+fn apply(value: Arg, filters: &[Filter], locale: &str) -> String {
+    let mut text = match value {
+        Arg::Text(text) => text.to_string(),
+        Arg::Number(n) => numbers::format_integer(n, locale),
+    };
+    for filter in filters {
+        text = match filter {
+            Filter::Upper => text.to_uppercase(),
+            Filter::Lower => text.to_lowercase(),
+            Filter::Title => title_case(&text),
+            Filter::Trim => text.trim().to_string(),
+            Filter::Ordinal => match value {
+                Arg::Number(n) => numbers::format_ordinal(n, locale),
+                Arg::Text(_) => text,
+            },
+            Filter::Plural(forms) => {
+                let category = match value {
+                    Arg::Number(n) => plural::category(n, locale),
+                    Arg::Text(_) => PluralCategory::Other,
+                };
+                forms
+                    .iter()
+                    .find(|(candidate, _)| *candidate == category)
+                    .or_else(|| {
+                        forms
+                            .iter()
+                            .find(|(candidate, _)| *candidate == PluralCategory::Other)
+                    })
+                    .map(|(_, word)| word.clone())
+                    .unwrap_or_default()
+            }
+            Filter::Default(fallback) if text.is_empty() => fallback.clone(),
+            Filter::Default(_) => text,
+        };
+    }
+    text
+}
+
+fn title_case(text: &str) -> String {
+    let mut capitalize = true;
+    text.chars()
+        .flat_map(|c| {
+            let out: Vec<char> = if capitalize {
+                c.to_uppercase().collect()
+            } else {
+                vec![c]
+            };
+            capitalize = c.is_whitespace() || c == '-';
+            out
+        })
+        .collect()
+}
+
+/// The template registered for `id` in exactly `locale`, if any.
+pub fn registered(locale: &str, id: &str) -> Option<Rc<Template>> {
+    let key = (registry_locale(locale).ok()?, id.to_string());
+    REGISTERED.with(|templates| templates.borrow().get(&key).cloned())
+}
+
+/// Locales that have at least one registered template.
+pub fn registered_locales() -> Vec<String> {
+    REGISTERED.with(|templates| {
+        templates
+            .borrow()
+            .keys()
+            .map(|(locale, _)| locale.clone())
+            .collect()
+    })
+}
+
+/// The registry key for `locale`, as `LanguageTag` writes it.
+fn registry_locale(locale: &str) -> Result<String, TemplateError> {
+    LanguageTag::parse(locale)
+        .map(|tag| tag.to_string())
+        .ok_or_else(|| TemplateError {
+            message: format!("`{}` is not a BCP-47 language tag", locale),
+            offset: 0,
+        })
+}
+
+/// Compiles a built-in catalog pattern once and reuses it afterwards.
+pub fn builtin(pattern: &'static str) -> Rc<Template> {
+    BUILTIN.with(|templates| {
+        templates
+            .borrow_mut()
+            .entry(pattern)
+            .or_insert_with(|| {
+                let template = Template::compile(pattern).unwrap_or_else(|_| Template {
+                    nodes: vec![Node::Text(pattern.to_string())],
+                });
+                Rc::new(template)
+            })
+            .clone()
+    })
+}
+
+/// Compiles `source` and registers it under `id` for `locale`, replacing the catalog message.
+///
+/// Rendering walks the locale's fallback chain as usual, so a template registered for `pt`
+/// also serves `pt-BR`, and one registered for `en` serves every locale without a message of
+/// its own. Placeholders are checked against those the built-in message receives, or against
+/// `placeholders` for ids the catalog does not know.
+pub fn register(
+    locale: &str,
+    id: &str,
+    source: &str,
+    placeholders: Option<&[String]>,
+) -> Result<(), TemplateError> {
+    let locale = registry_locale(locale)?;
+    let template = Template::compile(source)?;
+    let allowed: Vec<&str> = match (catalog::placeholders(id), placeholders) {
+        (Some(known), _) => known.to_vec(),
+        (None, Some(custom)) => custom.iter().map(String::as_str).collect(),
+        (None, None) => {
+            return Err(TemplateError {
+                message: format!("`{}` is not a built-in message; list its placeholders", id),
+                offset: 0,
+            })
+        }
+    };
+    if let Some((unknown, offset)) = template
+        .uses()
+        .into_iter()
+        .find(|(name, _)| !allowed.contains(name))
+    {
+        return Err(TemplateError {
+            message: format!(
+                "unknown placeholder `{}`; `{}` accepts {}",
+                unknown,
+                id,
+                allowed.join(", ")
+            ),
+            offset,
+        });
+    }
+    REGISTERED.with(|templates| {
+        templates
+            .borrow_mut()
+            .insert((locale, id.to_string()), Rc::new(template))
+    });
+    Ok(())
+}
+
+/// Removes the template registered for `id` in exactly `locale`.
+pub fn unregister(locale: &str, id: &str) -> bool {
+    let Ok(locale) = registry_locale(locale) else {
+        return false;
+    };
+    REGISTERED.with(|templates| {
+        templates
+            .borrow_mut()
+            .remove(&(locale, id.to_string()))
+            .is_some()
+    })
+}
+
+/// Message templates registered from JS, e.g. `Templates.register("greeting", "Hi {name|title}!")`.
+#[wasm_bindgen]
+pub struct Templates;
+
+#[wasm_bindgen]
+impl Templates {
+    /// Compiles and registers a template for `locale` (`en` when omitted); throws
+    /// `{ message, offset }` if it is invalid.
+    ///
+    /// `placeholders` is only needed for ids that are not built-in messages.
+    pub fn register(
+        id: &str,
+        source: &str,
+        placeholders: Option<Vec<String>>,
+        locale: Option<String>,
+    ) -> Result<(), JsValue> {
+        let locale = locale.as_deref().unwrap_or(DEFAULT_LOCALE);
+        register(locale, id, source, placeholders.as_deref())
+            .map_err(|error| js::to_js(&error).unwrap_or_else(JsValue::from))
+    }
+
+    pub fn unregister(id: &str, locale: Option<String>) -> bool {
+        unregister(locale.as_deref().unwrap_or(DEFAULT_LOCALE), id)
+    }
+
+    /// Renders the template registered for `id` nearest to `locale` on its fallback chain,
+    /// with the string and integer properties of `values`.
+    pub fn render(id: &str, values: JsValue, locale: Option<String>) -> Result<String, JsError> {
+        let locale = locale.as_deref().unwrap_or(DEFAULT_LOCALE);
+        let template = locale::fallback_chain(locale)
+            .iter()
+            .find_map(|candidate| registered(candidate, id))
+            .ok_or_else(|| JsError::new(&format!("no template is registered as `{}`", id)))?;
+        let values: HashMap<String, TemplateValue> = js::options_from_js(values)?;
+        let args: Vec<(&str, Arg)> = values
+            .iter()
+            .map(|(name, value)| {
+                let value = match value {
+                    TemplateValue::Number(n) => Arg::Number(*n),
+                    TemplateValue::Text(text) => Arg::Text(text),
+                };
+                (name.as_str(), value)
+            })
+            .collect();
+        Ok(template.render(&args, locale, &|text| text.to_string()))
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(untagged)]
+enum TemplateValue {
+    Number(i64),
+    Text(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(source: &str, args: &[(&str, Arg)], locale: &str) -> String {
+        Template::compile(source)
+            .unwrap()
+            .render(args, locale, &|text| text.to_string())
+    }
+
+    #[test]
+    fn rejects_unknown_placeholders_at_their_tag() {
+        let error = register("en", "greeting", "Hi age, {age}!", None).unwrap_err();
+        assert_eq!(error.offset, 8);
+        assert!(error.message.starts_with("unknown placeholder `age`"));
+        let error = register("en", "greeting", "Héllo{#if nom}!{/if}", None).unwrap_err();
+        assert_eq!(error.offset, 5);
+        let error = register("en", "custom", "{a}", None).unwrap_err();
+        assert_eq!(error.offset, 0);
+        let custom = ["a".to_string()];
+        assert!(register("en", "custom", "{a}", Some(&custom)).is_ok());
+    }
+
+    #[test]
+    fn reports_syntax_errors_with_character_offsets() {
+        let offset = |source| Template::compile(source).unwrap_err().offset;
+        assert_eq!(offset("é {name"), 2);
+        assert_eq!(offset("é }"), 2);
+        assert_eq!(offset("{#if a}x"), 0);
+        assert_eq!(offset("ab{/if}"), 2);
+        assert_eq!(offset("{name|shout}"), 0);
+        assert_eq!(offset("{1name}"), 0);
+    }
+
+    #[test]
+    fn applies_filters_in_order() {
+        let args = [
+            ("name", Arg::Text("  ada lovelace ")),
+            ("n", Arg::Number(21)),
+        ];
+        assert_eq!(render("{name|trim|title}", &args, "en"), "Ada Lovelace");
+        assert_eq!(render("{name|trim|upper}", &args, "en"), "ADA LOVELACE");
+        assert_eq!(render("{n|ordinal}", &args, "en"), "21st");
+        assert_eq!(render("{missing|default:none}", &args, "en"), "none");
+        assert_eq!(render("{missing}", &args, "en"), "{missing}");
+        assert_eq!(render("{{{n}}}", &args, "en"), "{21}");
+    }
+
+    #[test]
+    fn picks_plural_forms_by_locale() {
+        let source = "{n} {n|plural:one=год:few=года:many=лет:other=года}";
+        let years = |n| render(source, &[("n", Arg::Number(n))], "ru");
+        assert_eq!(years(1), "1 год");
+        assert_eq!(years(3), "3 года");
+        assert_eq!(years(11), "11 лет");
+        let en = |n| render("{n|plural:year:years}", &[("n", Arg::Number(n))], "en");
+        assert_eq!(en(1), "year");
+        assert_eq!(en(2), "years");
+    }
+
+    #[test]
+    fn branches_on_conditions() {
+        let source = "{#if years}{years}y{#else}now{/if}{#if !name} (anonymous){/if}";
+        assert_eq!(
+            render(source, &[("years", Arg::Number(2))], "en"),
+            "2y (anonymous)"
+        );
+        assert_eq!(
+            render(
+                source,
+                &[("years", Arg::Number(0)), ("name", Arg::Text("Ada"))],
+                "en"
+            ),
+            "now"
+        );
+    }
+
+    #[test]
+    fn registered_templates_follow_the_fallback_chain() {
+        let args = [("name", Arg::Text("Ana"))];
+        register("pt", "greeting", "E aí, {name}!", None).unwrap();
+        assert_eq!(
+            catalog::render("pt-BR", "greeting", &args, None),
+            "Oi, Ana!"
+        );
+        assert_eq!(
+            catalog::render("pt-PT", "greeting", &args, None),
+            "E aí, Ana!"
+        );
+        assert_eq!(
+            catalog::render("es", "greeting", &args, None),
+            "¡Hola, Ana!"
+        );
+        register("en", "greeting-morning", "Morning, {name}!", None).unwrap();
+        assert_eq!(
+            catalog::render("da", "greeting-morning", &args, None),
+            "Morning, Ana!"
+        );
+        assert!(unregister("pt", "greeting"));
+        assert!(!unregister("pt", "greeting"));
+        assert_eq!(
+            catalog::render("pt-PT", "greeting", &args, None),
+            "Olá, Ana!"
+        );
+        assert!(register("not a locale", "greeting", "{name}", None).is_err());
+    }
+}
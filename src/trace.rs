@@ -0,0 +1,431 @@
+use std::cmp::Ordering;
+
+use chrono::NaiveDate;
+use serde::{Serialize, Serializer};
+use wasm_bindgen::prelude::*;
+
+use crate::catalog::{self, Arg};
+use crate::convictions::{RightsStatus, VotingRights};
+use crate::dates;
+use crate::eligibility::EligibilityStatus;
+use crate::js;
+use crate::rules::VotingAgeRule;
+
+#[wasm_bindgen(typescript_custom_section)]
+const DECISION_TRACE_JSON: &'static str = r#"
+export interface DecisionTraceJSON {
+  inputs: {
+    age: number | null;
+    dateOfBirth: string | null;
+    on: string | null;
+  };
+  rules: TracedRuleJSON[];
+  comparisons: ComparisonJSON[];
+  verdict: {
+    status: "Eligible" | "NotYetEligible" | "JustEligible";
+    eligible: boolean;
+    votingRights: "Retained" | "Suspended" | "Restored" | "Lost" | null;
+    unmetConditions: string[];
+  };
+}
+
+export interface TracedRuleJSON {
+  kind: "voting-age" | "conviction";
+  id: string;
+  jurisdiction: string;
+  votingAge: number | null;
+  effectiveFrom: string | null;
+  effectiveTo: string | null;
+}
+
+export interface ComparisonJSON {
+  subject: "age" | "election-date" | "linked-election-date";
+  left: string;
+  right: string;
+  ordering: "Less" | "Equal" | "Greater";
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "DecisionTraceJSON")]
+    pub type DecisionTraceJson;
+}
+
+/// What an evaluation was given.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceInputs {
+    pub age: Option<i32>,
+    #[serde(serialize_with = "dates::serialize_iso_opt")]
+    pub date_of_birth: Option<NaiveDate>,
+    /// The election or reference date the age was taken on.
+    #[serde(serialize_with = "dates::serialize_iso_opt")]
+    pub on: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RuleKind {
+    VotingAge,
+    Conviction,
+}
+
+/// A rule an evaluation consulted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TracedRule {
+    pub kind: RuleKind,
+    pub id: String,
+    pub jurisdiction: String,
+    pub voting_age: Option<u8>,
+    #[serde(serialize_with = "dates::serialize_iso_opt")]
+    pub effective_from: Option<NaiveDate>,
+    #[serde(serialize_with = "dates::serialize_iso_opt")]
+    pub effective_to: Option<NaiveDate>,
+}
+
+impl TracedRule {
+    pub fn voting_age(rule: &VotingAgeRule) -> Self {
+        TracedRule {
+            kind: RuleKind::VotingAge,
+            id: rule.id(),
+            jurisdiction: rule.jurisdiction(),
+            voting_age: Some(rule.voting_age()),
+            effective_from: rule.effective_from(),
+            effective_to: rule.effective_to(),
+        }
+    }
+
+    pub fn conviction(rights: &VotingRights) -> Self {
+        TracedRule {
+            kind: RuleKind::Conviction,
+            id: format!(
+                "{}/convictions@{}",
+                rights.jurisdiction(),
+                rights.rules_version()
+            ),
+            jurisdiction: rights.jurisdiction(),
+            voting_age: None,
+            effective_from: None,
+            effective_to: None,
+        }
+    }
+}
+
+/// What a comparison set against each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Subject {
+    /// The age against the voting age.
+    Age,
+    /// The election date against the voting-age birthday.
+    ElectionDate,
+    /// The linked general election's date against the voting-age birthday.
+    LinkedElectionDate,
+}
+
+/// One `cmp` the evaluator made, as `left.cmp(&right)`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Comparison {
+    pub subject: Subject,
+    pub left: String,
+    pub right: String,
+    #[serde(serialize_with = "serialize_ordering")]
+    pub ordering: Ordering,
+}
+
+impl Comparison {
+    pub fn ages(age: i32, voting_age: u8) -> Self {
+        Comparison {
+            subject: Subject::Age,
+            left: age.to_string(),
+            right: voting_age.to_string(),
+            ordering: age.cmp(&i32::from(voting_age)),
+        }
+    }
+
+    pub fn dates(subject: Subject, date: NaiveDate, eligible_from: NaiveDate) -> Self {
+        Comparison {
+            subject,
+            left: date.to_string(),
+            right: eligible_from.to_string(),
+            ordering: date.cmp(&eligible_from),
+        }
+    }
+
+    fn message_id(&self) -> &'static str {
+        match (self.subject, self.ordering) {
+            (Subject::Age, Ordering::Less) => "trace-age-less",
+            (Subject::Age, Ordering::Equal) => "trace-age-equal",
+            (Subject::Age, Ordering::Greater) => "trace-age-greater",
+            (Subject::ElectionDate, Ordering::Less) => "trace-date-less",
+            (Subject::ElectionDate, Ordering::Equal) => "trace-date-equal",
+            (Subject::ElectionDate, Ordering::Greater) => "trace-date-greater",
+            (Subject::LinkedElectionDate, Ordering::Less) => "trace-linked-less",
+            (Subject::LinkedElectionDate, Ordering::Equal) => "trace-linked-equal",
+            (Subject::LinkedElectionDate, Ordering::Greater) => "trace-linked-greater",
+        }
+    }
+}
+
+fn serialize_ordering<S: Serializer>(
+    ordering: &Ordering,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(match ordering {
+        Ordering::Less => "Less",
+        Ordering::Equal => "Equal",
+        Ordering::Greater => "Greater",
+    })
+}
+
+/// The steps an evaluation records as it goes; the verdict is added when the trace is read.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Steps {
+    pub inputs: TraceInputs,
+    pub rules: Vec<TracedRule>,
+    pub comparisons: Vec<Comparison>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TraceVerdict {
+    status: EligibilityStatus,
+    eligible: bool,
+    voting_rights: Option<RightsStatus>,
+    unmet_conditions: Vec<String>,
+}
+
+/// Why an evaluation reached its verdict: its inputs, the rules it consulted, every
+/// comparison it made and the verdict itself.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DecisionTrace {
+    inputs: TraceInputs,
+    rules: Vec<TracedRule>,
+    comparisons: Vec<Comparison>,
+    verdict: TraceVerdict,
+}
+
+impl DecisionTrace {
+    pub fn new(
+        steps: &Steps,
+        status: EligibilityStatus,
+        eligible: bool,
+        voting_rights: Option<RightsStatus>,
+        unmet_conditions: &[String],
+    ) -> Self {
+        DecisionTrace {
+            inputs: steps.inputs.clone(),
+            rules: steps.rules.clone(),
+            comparisons: steps.comparisons.clone(),
+            verdict: TraceVerdict {
+                status,
+                eligible,
+                voting_rights,
+                unmet_conditions: unmet_conditions.to_vec(),
+            },
+        }
+    }
+
+    pub fn rules(&self) -> &[TracedRule] {
+        &self.rules
+    }
+
+    pub fn comparisons(&self) -> &[Comparison] {
+        &self.comparisons
+    }
+
+    fn threshold(&self) -> i64 {
+        self.rules
+            .iter()
+            .find_map(|rule| rule.voting_age)
+            .unwrap_or_default()
+            .into()
+    }
+
+    /// Every step as a sentence in `locale`, rules first and the verdict last.
+    pub fn sentences(&self, locale: &str) -> Vec<String> {
+        let threshold = self.threshold();
+        let mut sentences = Vec::new();
+        for rule in &self.rules {
+            let from = rule
+                .effective_from
+                .map(|date| date.to_string())
+                .unwrap_or_default();
+            let to = rule
+                .effective_to
+                .map(|date| date.to_string())
+                .unwrap_or_default();
+            let id = match rule.kind {
+                RuleKind::VotingAge => "trace-rule",
+                RuleKind::Conviction => "trace-conviction-rule",
+            };
+            sentences.push(catalog::render(
+                locale,
+                id,
+                &[
+                    ("rule", Arg::Text(&rule.id)),
+                    ("jurisdiction", Arg::Text(&rule.jurisdiction)),
+                    (
+                        "threshold",
+                        Arg::Number(rule.voting_age.unwrap_or_default().into()),
+                    ),
+                    ("from", Arg::Text(&from)),
+                    ("to", Arg::Text(&to)),
+                ],
+                None,
+            ));
+        }
+        for comparison in &self.comparisons {
+            let (left, right) = (comparison.left.as_str(), comparison.right.as_str());
+            let args = match comparison.subject {
+                Subject::Age => vec![
+                    ("age", Arg::Number(left.parse().unwrap_or_default())),
+                    ("threshold", Arg::Number(threshold)),
+                ],
+                Subject::ElectionDate | Subject::LinkedElectionDate => vec![
+                    ("date", Arg::Text(left)),
+                    ("birthday", Arg::Text(right)),
+                    ("threshold", Arg::Number(threshold)),
+                ],
+            };
+            sentences.push(catalog::render(
+                locale,
+                comparison.message_id(),
+                &args,
+                Some(threshold),
+            ));
+        }
+        if let Some(rights) = self.verdict.voting_rights {
+            let id = match rights {
+                RightsStatus::Retained | RightsStatus::Restored => "trace-record-allowed",
+                RightsStatus::Suspended | RightsStatus::Lost => "trace-record-barred",
+            };
+            sentences.push(catalog::render(locale, id, &[], None));
+        }
+        if !self.verdict.unmet_conditions.is_empty() {
+            let conditions = self.verdict.unmet_conditions.join(", ");
+            sentences.push(catalog::render(
+                locale,
+                "trace-conditions-unmet",
+                &[("conditions", Arg::Text(&conditions))],
+                None,
+            ));
+        }
+        let id = if self.verdict.eligible {
+            "trace-verdict-eligible"
+        } else {
+            "trace-verdict-ineligible"
+        };
+        sentences.push(catalog::render(locale, id, &[], None));
+        sentences
+    }
+}
+
+#[wasm_bindgen]
+impl DecisionTrace {
+    #[wasm_bindgen(getter)]
+    pub fn eligible(&self) -> bool {
+        self.verdict.eligible
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn status(&self) -> EligibilityStatus {
+        self.verdict.status
+    }
+
+    /// Ids of the rules consulted, voting-age rule first.
+    #[wasm_bindgen(getter, js_name = ruleIds)]
+    pub fn rule_ids(&self) -> Vec<String> {
+        self.rules.iter().map(|rule| rule.id.clone()).collect()
+    }
+
+    /// The trace as prose in `locale`, one sentence per step.
+    pub fn explain(&self, locale: &str) -> String {
+        self.sentences(locale).join(" ")
+    }
+
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json(&self) -> Result<DecisionTraceJson, JsError> {
+        Ok(js::to_js(self)?.unchecked_into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eligibility::{check_in, check_on, check_with_record};
+
+    fn date(text: &str) -> NaiveDate {
+        dates::parse_iso_date(text).unwrap()
+    }
+
+    #[test]
+    fn explains_an_age_check() {
+        let trace = check_in("AT", 15).unwrap().trace();
+        assert_eq!(trace.rule_ids(), ["AT/16@2007-07-01"]);
+        assert_eq!(
+            trace.sentences("en"),
+            [
+                "Rule AT/16@2007-07-01 applies in AT from 2007-07-01: the voting age is 16.",
+                "Age 15 is below the voting age of 16.",
+                "Verdict: not eligible to vote.",
+            ]
+        );
+    }
+
+    #[test]
+    fn explains_a_past_rule_by_its_dates() {
+        let trace = check_on("JP", date("1996-01-01"), date("2016-01-10"))
+            .unwrap()
+            .trace();
+        assert!(trace.eligible());
+        assert_eq!(trace.status(), EligibilityStatus::Eligible);
+        assert_eq!(trace.rules()[0].effective_to, Some(date("2016-06-18")));
+        assert_eq!(
+            trace.sentences("en")[1..],
+            [
+                "They turned 20 on 2016-01-01, before 2016-01-10.",
+                "Verdict: eligible to vote."
+            ]
+        );
+    }
+
+    #[test]
+    fn records_the_criminal_record_check() {
+        let convictions = serde_json::from_str::<Vec<_>>(
+            r#"[{"status": "incarcerated", "releaseDate": "2027-01-01"}]"#,
+        )
+        .unwrap();
+        let trace = check_with_record(
+            "US-CA",
+            date("1990-01-01"),
+            &convictions,
+            date("2026-11-03"),
+        )
+        .unwrap()
+        .trace();
+        assert_eq!(trace.rules()[1].kind, RuleKind::Conviction);
+        assert!(!trace.eligible());
+        assert_eq!(trace.status(), EligibilityStatus::Eligible);
+        let explanation = trace.explain("en");
+        assert!(explanation.contains("Conviction rules US-CA/convictions@"));
+        assert!(explanation
+            .ends_with("Their criminal record stops them voting. Verdict: not eligible to vote."));
+    }
+
+    #[test]
+    fn serializes_orderings_and_dates_as_strings() {
+        let trace = check_on("US", date("2008-11-03"), date("2026-11-03"))
+            .unwrap()
+            .trace();
+        let json = serde_json::to_value(&trace).unwrap();
+        assert_eq!(json["inputs"]["dateOfBirth"], "2008-11-03");
+        assert_eq!(json["comparisons"][0]["subject"], "election-date");
+        assert_eq!(json["comparisons"][0]["ordering"], "Equal");
+        assert_eq!(json["rules"][0]["kind"], "voting-age");
+        assert_eq!(json["verdict"]["status"], "JustEligible");
+    }
+}
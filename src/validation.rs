@@ -0,0 +1,205 @@
+use std::fmt;
+
+use csv::WriterBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::error::EligibilityError;
+
+/// Oldest age accepted from an age column.
+pub const MAX_AGE: i32 = 150;
+
+/// What went wrong with a voter-roll cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ValidationErrorKind {
+    /// The record is too short to contain the column.
+    MissingColumn,
+    /// The cell is empty.
+    MissingValue,
+    NotANumber,
+    /// The age is negative or above `MAX_AGE`.
+    AgeOutOfRange,
+    InvalidDate,
+    BornAfterReference,
+    UnknownJurisdiction,
+    /// The jurisdiction is known but had no voting-age rule on the election date.
+    NoRuleInForce,
+    /// The voting-age birthday falls outside the supported calendar.
+    DateOutOfRange,
+    /// The record itself could not be parsed.
+    MalformedRecord,
+}
+
+impl ValidationErrorKind {
+    /// The kind reported for a cell whose value failed with `error`.
+    pub fn of(error: &EligibilityError) -> Self {
+        match error {
+            EligibilityError::UnknownJurisdiction(_) => ValidationErrorKind::UnknownJurisdiction,
+            EligibilityError::InvalidDate(_) => ValidationErrorKind::InvalidDate,
+            EligibilityError::BornAfterReference { .. } => ValidationErrorKind::BornAfterReference,
+            EligibilityError::MissingColumn(_) => ValidationErrorKind::MissingColumn,
+            EligibilityError::NoRuleInForce { .. } => ValidationErrorKind::NoRuleInForce,
+            EligibilityError::DateOutOfRange { .. } => ValidationErrorKind::DateOutOfRange,
+            EligibilityError::InvalidRow(error) => error.kind,
+            _ => ValidationErrorKind::MalformedRecord,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ValidationErrorKind::MissingColumn => "MissingColumn",
+            ValidationErrorKind::MissingValue => "MissingValue",
+            ValidationErrorKind::NotANumber => "NotANumber",
+            ValidationErrorKind::AgeOutOfRange => "AgeOutOfRange",
+            ValidationErrorKind::InvalidDate => "InvalidDate",
+            ValidationErrorKind::BornAfterReference => "BornAfterReference",
+            ValidationErrorKind::UnknownJurisdiction => "UnknownJurisdiction",
+            ValidationErrorKind::NoRuleInForce => "NoRuleInForce",
+            ValidationErrorKind::DateOutOfRange => "DateOutOfRange",
+            ValidationErrorKind::MalformedRecord => "MalformedRecord",
+        }
+    }
+}
+
+/// A validation failure tied to a row and, where known, a column of the input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RowError {
+    /// Line of the input the row starts on; the header is line 1.
+    pub row: u64,
+    pub column: Option<String>,
+    pub value: String,
+    pub kind: ValidationErrorKind,
+    pub message: String,
+}
+
+impl RowError {
+    pub fn new(row: u64, column: &str, value: &str, kind: ValidationErrorKind) -> Self {
+        let message = match kind {
+            ValidationErrorKind::MissingColumn => format!("record has no `{}` column", column),
+            ValidationErrorKind::MissingValue => format!("`{}` is empty", column),
+            ValidationErrorKind::NotANumber => format!("`{}` is not a number", value),
+            ValidationErrorKind::AgeOutOfRange => {
+                format!("age {} is outside 0..={}", value, MAX_AGE)
+            }
+            ValidationErrorKind::InvalidDate => format!("`{}` is not an ISO-8601 date", value),
+            ValidationErrorKind::BornAfterReference => {
+                format!("date of birth {} is after the election date", value)
+            }
+            ValidationErrorKind::UnknownJurisdiction => {
+                format!("unknown jurisdiction `{}`", value)
+            }
+            ValidationErrorKind::NoRuleInForce => {
+                format!("no rule for `{}` was in force on the election date", value)
+            }
+            ValidationErrorKind::DateOutOfRange => {
+                format!("`{}` is too close to the end of the calendar", value)
+            }
+            ValidationErrorKind::MalformedRecord => value.to_string(),
+        };
+        RowError {
+            row,
+            column: Some(column.to_string()).filter(|column| !column.is_empty()),
+            value: value.to_string(),
+            kind,
+            message,
+        }
+    }
+
+    pub fn malformed(error: &csv::Error) -> Self {
+        let row = error.position().map_or(0, |position| position.line());
+        RowError::new(
+            row,
+            "",
+            &error.to_string(),
+            ValidationErrorKind::MalformedRecord,
+        )
+    }
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.column {
+            Some(column) => write!(f, "row {}, column `{}`: {}", self.row, column, self.message),
+            None => write!(f, "row {}: {}", self.row, self.message),
+        }
+    }
+}
+
+/// Whether invalid rows are collected or stop processing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ErrorMode {
+    #[default]
+    Collect,
+    FailFast,
+}
+
+impl ErrorMode {
+    /// Hands back `error` to collect, or turns it into a hard failure in fail-fast mode.
+    pub fn handle(self, error: RowError) -> Result<RowError, EligibilityError> {
+        match self {
+            ErrorMode::Collect => Ok(error),
+            ErrorMode::FailFast => Err(EligibilityError::InvalidRow(Box::new(error))),
+        }
+    }
+}
+
+/// Writes validation errors as CSV with a `row,column,value,kind,message` header.
+pub fn write_report(errors: &[RowError]) -> Result<String, EligibilityError> {
+    let mut writer = WriterBuilder::new().from_writer(Vec::new());
+    writer.write_record(["row", "column", "value", "kind", "message"])?;
+    for error in errors {
+        writer.write_record([
+            error.row.to_string().as_str(),
+            error.column.as_deref().unwrap_or_default(),
+            &error.value,
+            error.kind.as_str(),
+            &error.message,
+        ])?;
+    }
+    let bytes = writer
+        .into_inner()
+        .map_err(|error| EligibilityError::Csv(error.to_string()))?;
+    String::from_utf8(bytes).map_err(|error| EligibilityError::Csv(error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_name_the_value() {
+        let error = RowError::new(7, "age", "-3", ValidationErrorKind::AgeOutOfRange);
+        assert_eq!(
+            error.to_string(),
+            "row 7, column `age`: age -3 is outside 0..=150"
+        );
+        let error = RowError::new(2, "", "bad quote", ValidationErrorKind::MalformedRecord);
+        assert_eq!(error.column, None);
+        assert_eq!(error.to_string(), "row 2: bad quote");
+    }
+
+    #[test]
+    fn fail_fast_turns_row_errors_into_failures() {
+        let error = RowError::new(3, "jurisdiction", "AR", ValidationErrorKind::NoRuleInForce);
+        assert_eq!(ErrorMode::Collect.handle(error.clone()), Ok(error.clone()));
+        assert_eq!(
+            ErrorMode::FailFast.handle(error.clone()),
+            Err(EligibilityError::InvalidRow(Box::new(error)))
+        );
+    }
+
+    #[test]
+    fn writes_errors_as_csv() {
+        let errors = [RowError::new(
+            4,
+            "age",
+            "x,y",
+            ValidationErrorKind::NotANumber,
+        )];
+        assert_eq!(
+            write_report(&errors).unwrap(),
+            "row,column,value,kind,message\n4,age,\"x,y\",NotANumber,\"`x,y` is not a number\"\n"
+        );
+    }
+}